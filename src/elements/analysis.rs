@@ -0,0 +1,116 @@
+use crate::elements::complex::ElementId;
+use crate::elements::wire::Wire;
+use std::fmt::Display;
+
+/// Structural report produced by `Complex::compile`.
+#[derive(Debug, Clone, Default)]
+pub struct Analysis {
+    pub feedback: Vec<Feedback>,
+}
+
+/// A set of elements that feed their own inputs, e.g. the cross-coupled gates of a latch.
+#[derive(Debug, Clone)]
+pub struct Feedback {
+    /// Complex types from the analyzed complex down to the one that holds the loop.
+    pub path: Vec<&'static str>,
+    /// Handles of the looped elements within the innermost complex.
+    pub elements: Vec<ElementId>,
+    /// Types of the looped elements, in the same order as `elements`.
+    pub kinds: Vec<&'static str>,
+    /// Wires that are both driven and read inside the loop.
+    pub wires: Vec<Wire>,
+    /// Names of `wires` by label or by their number in the `nets` of the innermost complex.
+    pub names: Vec<String>,
+}
+
+impl Analysis {
+    pub fn has_feedback(&self) -> bool {
+        !self.feedback.is_empty()
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for feedback in &self.feedback {
+            writeln!(f, "{}", feedback)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.path.join("/"))?;
+        for (i, (idx, kind)) in self.elements.iter().zip(&self.kinds).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}#{}", kind, idx)?;
+        }
+        write!(f, " via {}", self.names.join(", "))
+    }
+}
+
+/// Returns the strongly connected components of `successors` that form a cycle.
+pub(crate) fn cycles(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let len = successors.len();
+    let mut index = vec![usize::MAX; len];
+    let mut low = vec![0; len];
+    let mut on_stack = vec![false; len];
+    let mut stack = Vec::new();
+    let mut counter = 0;
+    let mut cycles = Vec::new();
+
+    for root in 0..len {
+        if index[root] != usize::MAX {
+            continue;
+        }
+
+        let mut calls = vec![(root, 0)];
+        index[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&(node, next)) = calls.last() {
+            if let Some(&succ) = successors[node].get(next) {
+                calls.last_mut().unwrap().1 += 1;
+                if index[succ] == usize::MAX {
+                    index[succ] = counter;
+                    low[succ] = counter;
+                    counter += 1;
+                    stack.push(succ);
+                    on_stack[succ] = true;
+                    calls.push((succ, 0));
+                } else if on_stack[succ] {
+                    low[node] = low[node].min(index[succ]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+            if low[node] != index[node] {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            while let Some(member) = stack.pop() {
+                on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            if component.len() > 1 || successors[node].contains(&node) {
+                component.sort_unstable();
+                cycles.push(component);
+            }
+        }
+    }
+
+    cycles
+}
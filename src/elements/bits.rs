@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+/// Vector of bits of any width, for buses wider than the integer types.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Bits {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bits {
+    /// Returns `len` low bits.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "bit {} out of {}", index, self.len);
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "bit {} out of {}", index, self.len);
+        let bit = 1 << (index % 64);
+        if value {
+            self.words[index / 64] |= bit;
+        } else {
+            self.words[index / 64] &= !bit;
+        }
+    }
+
+    pub fn push(&mut self, value: bool) {
+        if self.len == self.words.len() * 64 {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|index| self.get(index))
+    }
+}
+
+impl FromIterator<bool> for Bits {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = Bits::default();
+        for value in iter {
+            bits.push(value);
+        }
+        bits
+    }
+}
+
+impl From<&[bool]> for Bits {
+    fn from(values: &[bool]) -> Self {
+        values.iter().copied().collect()
+    }
+}
+
+/// Prints bit 0 first, matching the wire order of a bus.
+impl Display for Bits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for value in self.iter() {
+            write!(f, "{}", value as u8)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::elements::bus::Bus;
+use crate::elements::complex::{Complex, Element, ElementId};
+use crate::elements::component::Component;
+use crate::elements::error::BinariiError;
+use crate::elements::gate::Gate;
+use crate::elements::wire::Wire;
+
+/// Collects the elements of a `Complex` and compiles the hierarchy once in `finish`.
+///
+/// `Complex::add_gate` and friends recompile after every call, which gets slow for large
+/// circuits.
+pub struct ComplexBuilder {
+    complex: Complex,
+}
+
+impl ComplexBuilder {
+    pub fn new(tp: &'static str) -> Self {
+        Self {
+            complex: Complex::new(tp),
+        }
+    }
+
+    pub fn add_input(&mut self, wire: Wire) -> usize {
+        self.complex.add_input(wire)
+    }
+
+    pub fn add_input_bus(&mut self, bus: Bus) {
+        self.complex.add_input_bus(bus);
+    }
+
+    pub fn add_output(&mut self, wire: Wire) -> usize {
+        self.complex.add_output(wire)
+    }
+
+    pub fn add_output_bus(&mut self, bus: Bus) {
+        self.complex.add_output_bus(bus);
+    }
+
+    pub fn add_named_input(
+        &mut self,
+        name: &'static str,
+        wire: Wire,
+    ) -> Result<usize, BinariiError> {
+        self.complex.add_named_input(name, wire)
+    }
+
+    pub fn add_named_input_bus(
+        &mut self,
+        name: &'static str,
+        bus: Bus,
+    ) -> Result<(), BinariiError> {
+        self.complex.add_named_input_bus(name, bus)
+    }
+
+    pub fn add_named_output(
+        &mut self,
+        name: &'static str,
+        wire: Wire,
+    ) -> Result<usize, BinariiError> {
+        self.complex.add_named_output(name, wire)
+    }
+
+    pub fn add_named_output_bus(
+        &mut self,
+        name: &'static str,
+        bus: Bus,
+    ) -> Result<(), BinariiError> {
+        self.complex.add_named_output_bus(name, bus)
+    }
+
+    pub fn add_gate(&mut self, gate: Gate) -> ElementId {
+        self.complex.push(Element::Gate(gate))
+    }
+
+    pub fn add_complex(&mut self, complex: Complex) -> ElementId {
+        self.complex.push(Element::Complex(complex))
+    }
+
+    pub fn add_component(&mut self, component: impl Component + 'static) -> ElementId {
+        self.complex.push(Element::Component(Box::new(component)))
+    }
+
+    pub fn add_element(&mut self, element: Element) -> ElementId {
+        self.complex.push(element)
+    }
+
+    pub fn get_in(&self, wire_id: usize) -> Wire {
+        self.complex.get_in(wire_id)
+    }
+
+    pub fn get_out(&self, wire_id: usize) -> Wire {
+        self.complex.get_out(wire_id)
+    }
+
+    pub fn get_in_bus(&self, offset: usize, len: usize) -> Bus {
+        self.complex.get_in_bus(offset, len)
+    }
+
+    pub fn get_out_bus(&self, offset: usize, len: usize) -> Bus {
+        self.complex.get_out_bus(offset, len)
+    }
+
+    pub fn set_in(&mut self, wire_id: usize, wire: Wire) {
+        self.complex.rewire_in(wire_id, wire, false);
+    }
+
+    pub fn set_out(&mut self, wire_id: usize, wire: Wire) {
+        self.complex.rewire_out(wire_id, wire, false);
+    }
+
+    pub fn finish(mut self) -> Complex {
+        self.complex.compile_deep();
+        self.complex
+    }
+}
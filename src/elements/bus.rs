@@ -1,20 +1,52 @@
+use crate::elements::bits::Bits;
+use crate::elements::error::BinariiError;
+use crate::elements::signal::LANES;
 use crate::elements::wire::Wire;
 use std::fmt::Display;
 
+/// Which end of an integer the first wire of its field on a bus carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BitOrder {
+    /// The first wire is the most significant bit.
+    #[default]
+    MsbFirst,
+    /// The first wire is the least significant bit.
+    LsbFirst,
+}
+
 #[derive(Debug, Clone)]
 pub struct Bus {
     wires: Vec<Wire>,
+    order: BitOrder,
 }
 
 impl Bus {
     pub fn new(wires: usize) -> Self {
         Self {
             wires: (0..wires).map(|_| Wire::new()).collect(),
+            order: BitOrder::default(),
         }
     }
 
     pub fn with_wires(wires: Vec<Wire>) -> Self {
-        Self { wires }
+        Self {
+            wires,
+            order: BitOrder::default(),
+        }
+    }
+
+    /// Sets the bit order used by the integer accessors.
+    pub fn with_order(mut self, order: BitOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn set_order(&mut self, order: BitOrder) {
+        self.order = order;
+    }
+
+    pub fn order(&self) -> BitOrder {
+        self.order
     }
 
     pub fn get_wire(&self, index: usize) -> Wire {
@@ -40,11 +72,52 @@ impl Bus {
     pub fn size(&self) -> usize {
         self.wires.len()
     }
+
+    pub fn try_get_wire(&self, index: usize) -> Result<Wire, BinariiError> {
+        self.wires
+            .get(index)
+            .cloned()
+            .ok_or(BinariiError::WireOutOfRange {
+                index,
+                len: self.wires.len(),
+            })
+    }
+
+    /// Number of wires an integer of `bits` bits takes from `offset`.
+    fn int_width(&self, offset: usize, bits: u32) -> usize {
+        self.wires.len().saturating_sub(offset).min(bits as usize)
+    }
+
+    /// Returns the wire carrying bit `bit`, counted from the most significant, of the
+    /// `width`-bit field at `offset`.
+    fn bit_wire(&self, offset: usize, width: usize, bit: usize) -> &Wire {
+        match self.order {
+            BitOrder::MsbFirst => &self.wires[offset + bit],
+            BitOrder::LsbFirst => &self.wires[offset + width - 1 - bit],
+        }
+    }
+
+    /// Checks that the bus has `width` wires starting at `offset`.
+    fn check_width(&self, offset: usize, width: usize) -> Result<(), BinariiError> {
+        let found = self.wires.len().saturating_sub(offset);
+        if found < width {
+            return Err(BinariiError::WidthMismatch {
+                expected: width,
+                found,
+            });
+        }
+        Ok(())
+    }
 }
 
 pub trait BusAccess<T> {
     fn set(&self, offset: usize, value: T);
     fn get(&self, offset: usize) -> T;
+
+    /// Like `set`, but fails when the bus has too few wires from `offset` for a `T`.
+    fn try_set(&self, offset: usize, value: T) -> Result<(), BinariiError>;
+
+    fn try_get(&self, offset: usize) -> Result<T, BinariiError>;
 }
 
 impl BusAccess<bool> for Bus {
@@ -55,30 +128,158 @@ impl BusAccess<bool> for Bus {
     fn get(&self, offset: usize) -> bool {
         self.wires[offset].get()
     }
+
+    fn try_set(&self, offset: usize, value: bool) -> Result<(), BinariiError> {
+        self.check_width(offset, 1)?;
+        self.set(offset, value);
+        Ok(())
+    }
+
+    fn try_get(&self, offset: usize) -> Result<bool, BinariiError> {
+        self.check_width(offset, 1)?;
+        Ok(self.get(offset))
+    }
 }
 
-impl BusAccess<u8> for Bus {
-    fn set(&self, offset: usize, value: u8) {
-        for i in 0..8 {
-            let offset = offset + i;
-            if offset < self.wires.len() {
-                self.wires[offset].set((value >> (7 - i) & 1) == 1);
+/// Integers are written in the bit order of the bus, two's complement for the signed types.
+/// A bus shorter than the integer carries its low bits, except for `u8`, which keeps its
+/// original truncation to the high bits. `get` reads the wires back as a narrower number,
+/// sign-extended for the signed types. `try_set` and `try_get` require all the bits of the
+/// type from `offset`, whatever the value.
+macro_rules! impl_int_access {
+    ($high:literal: $($t:ty),*) => {$(
+        impl BusAccess<$t> for Bus {
+            fn set(&self, offset: usize, value: $t) {
+                let width = self.int_width(offset, <$t>::BITS);
+                let top = if $high { <$t>::BITS as usize } else { width };
+                for i in 0..width {
+                    self.bit_wire(offset, width, i).set((value >> (top - 1 - i) & 1) == 1);
+                }
+            }
+
+            fn get(&self, offset: usize) -> $t {
+                let width = self.int_width(offset, <$t>::BITS);
+                let mut value: $t = 0;
+                for i in 0..width {
+                    value <<= 1;
+                    if self.bit_wire(offset, width, i).get() {
+                        value |= 1;
+                    }
+                }
+                sign_extend!(value, width)
+            }
+
+            fn try_set(&self, offset: usize, value: $t) -> Result<(), BinariiError> {
+                self.check_width(offset, <$t>::BITS as usize)?;
+                self.set(offset, value);
+                Ok(())
+            }
+
+            fn try_get(&self, offset: usize) -> Result<$t, BinariiError> {
+                self.check_width(offset, <$t>::BITS as usize)?;
+                Ok(self.get(offset))
             }
         }
-    }
 
-    fn get(&self, offset: usize) -> u8 {
-        let mut value = 0;
-        for i in 0..8 {
-            let offset = offset + i;
-            if offset < self.wires.len() {
-                value <<= 1;
-                if self.wires[offset].get() {
-                    value |= 1;
+        impl LaneAccess<$t> for Bus {
+            fn set_lanes(&self, offset: usize, values: &[$t]) {
+                let width = self.int_width(offset, <$t>::BITS);
+                let top = if $high { <$t>::BITS as usize } else { width };
+                for i in 0..width {
+                    let lanes = values
+                        .iter()
+                        .take(LANES)
+                        .enumerate()
+                        .fold(0, |lanes, (lane, value)| {
+                            lanes | ((value >> (top - 1 - i) & 1) as u64) << lane
+                        });
+                    self.bit_wire(offset, width, i).set_lanes(lanes);
+                }
+            }
+
+            fn get_lanes(&self, offset: usize) -> [$t; LANES] {
+                let width = self.int_width(offset, <$t>::BITS);
+                let mut values = [0; LANES];
+                for i in 0..width {
+                    let lanes = self.bit_wire(offset, width, i).lanes();
+                    for (lane, value) in values.iter_mut().enumerate() {
+                        *value = *value << 1 | (lanes >> lane & 1) as $t;
+                    }
                 }
+                values.map(|value| sign_extend!(value, width))
             }
         }
-        value
+    )*};
+}
+
+/// Copies bit `width - 1` into the bits above it; a no-op for unsigned values.
+macro_rules! sign_extend {
+    ($value:expr, $width:expr) => {{
+        let value = $value;
+        let shift = std::mem::size_of_val(&value) * 8 - $width;
+        if $width == 0 {
+            value
+        } else {
+            value << shift >> shift
+        }
+    }};
+}
+
+impl_int_access!(true: u8);
+impl_int_access!(false: u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Bit `i` of the vector goes to the wire at `offset + i`; `get` reads to the end of the bus.
+impl BusAccess<Bits> for Bus {
+    fn set(&self, offset: usize, value: Bits) {
+        for (wire, bit) in self.wires.iter().skip(offset).zip(value.iter()) {
+            wire.set(bit);
+        }
+    }
+
+    fn get(&self, offset: usize) -> Bits {
+        self.wires
+            .iter()
+            .skip(offset)
+            .map(|wire| wire.get())
+            .collect()
+    }
+
+    fn try_set(&self, offset: usize, value: Bits) -> Result<(), BinariiError> {
+        self.check_width(offset, value.len())?;
+        self.set(offset, value);
+        Ok(())
+    }
+
+    fn try_get(&self, offset: usize) -> Result<Bits, BinariiError> {
+        if offset > self.wires.len() {
+            return Err(BinariiError::WireOutOfRange {
+                index: offset,
+                len: self.wires.len(),
+            });
+        }
+        Ok(self.get(offset))
+    }
+}
+
+/// Loads and reads independent values in every simulation lane of the bus wires.
+pub trait LaneAccess<T> {
+    fn set_lanes(&self, offset: usize, values: &[T]);
+    fn get_lanes(&self, offset: usize) -> [T; LANES];
+}
+
+impl LaneAccess<bool> for Bus {
+    fn set_lanes(&self, offset: usize, values: &[bool]) {
+        let lanes = values
+            .iter()
+            .take(LANES)
+            .enumerate()
+            .fold(0, |lanes, (lane, value)| lanes | (*value as u64) << lane);
+        self.wires[offset].set_lanes(lanes);
+    }
+
+    fn get_lanes(&self, offset: usize) -> [bool; LANES] {
+        let lanes = self.wires[offset].lanes();
+        std::array::from_fn(|lane| (lanes >> lane) & 1 == 1)
     }
 }
 
@@ -1,29 +1,153 @@
-use crate::elements::bus::Bus;
+use crate::elements::analysis::{self, Analysis, Feedback};
+use crate::elements::builder::ComplexBuilder;
+use crate::elements::bus::{BitOrder, Bus};
+use crate::elements::component::Component;
+use crate::elements::error::BinariiError;
 use crate::elements::gate::Gate;
-use crate::elements::wire::Wire;
+use crate::elements::netlist::Netlist;
+use crate::elements::observer::{ObserverId, Observers, WireChange};
+use crate::elements::program::Program;
+use crate::elements::signal::{Signal, WireId};
+use crate::elements::snapshot::Snapshot;
+use crate::elements::wire::{Wire, WireMap};
 use crate::elements::Conduct;
-use bevy::utils::HashMap;
-use std::fmt::{Debug, Display};
+use bevy::utils::{HashMap, HashSet};
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
 use std::mem;
 
+pub const DEFAULT_SETTLE_LIMIT: usize = 256;
+
 pub struct Complex {
     input: Vec<Wire>,
     output: Vec<Wire>,
+    ports: Box<Ports>,
     gates: Vec<Element>,
+    ids: Vec<ElementId>,
+    next_id: usize,
     tp: &'static str,
     iters_per_tick: usize,
+    settle_limit: usize,
+    mode: Mode,
+    nets: Box<Nets>,
+    state: Box<RefCell<State>>,
+    observers: Box<RefCell<Observers>>,
+}
+
+/// A named run of consecutive input or output wires of a `Complex`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Port {
+    pub name: &'static str,
+    pub offset: usize,
+    pub width: usize,
+    /// Bit order of the bus the port was added with, given back by the lookups by name.
+    pub order: BitOrder,
+}
+
+#[derive(Default, Clone)]
+struct Ports {
+    input: Vec<Port>,
+    output: Vec<Port>,
+}
+
+/// Handle of an element inside a `Complex`, stable across recompilation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ElementId(usize);
+
+impl Display for ElementId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Every element is evaluated `iters_per_tick` times per tick.
+    Iterative,
+    /// Only elements whose input wires changed since their last evaluation are evaluated.
+    EventDriven,
+}
+
+/// Returned by `Complex::settle` when the wires keep changing after the iteration limit.
+#[derive(Debug, Clone)]
+pub struct Oscillation {
+    pub complex: &'static str,
+    pub iterations: usize,
+    pub wires: Vec<Wire>,
+    /// Names of `wires` by label or by their number in the `nets` of the complex.
+    pub names: Vec<String>,
+}
+
+impl Display for Oscillation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} did not settle after {} iterations, toggling wires: [",
+            self.complex, self.iterations
+        )?;
+        write!(f, "{}]", self.names.join(", "))
+    }
+}
+
+impl Error for Oscillation {}
+
+/// Wire topology of the compiled elements, indexed by net.
+#[derive(Default)]
+struct Nets {
+    wires: Vec<Wire>,
+    fanout: Vec<Vec<usize>>,
+    drivers: Vec<Vec<usize>>,
+    outputs: Vec<Vec<usize>>,
+    /// Per feedback loop, the looped elements and the nets the loop passes through.
+    cycles: Vec<(Vec<usize>, Vec<usize>)>,
+    /// Per input port, the output ports it reaches within a tick.
+    reach: Vec<Vec<usize>>,
+    /// Nets with more than one driver.
+    shared: Vec<usize>,
+    /// Per element, the shared nets it drives as `(index into shared, driver slot)`.
+    drives: Vec<Vec<(usize, usize)>>,
+    /// Per element, whether it is or holds a stateful component that changes every tick.
+    clocked: Vec<bool>,
+}
+
+impl Nets {
+    /// Returns the state of a complex that has not ticked yet.
+    fn state(&self) -> State {
+        State {
+            drives: self
+                .shared
+                .iter()
+                .map(|&net| vec![Signal::Z; self.drivers[net].len()])
+                .collect(),
+            fighting: vec![0; self.shared.len()],
+            ..State::default()
+        }
+    }
+}
+
+/// Simulation state carried between ticks.
+#[derive(Default)]
+struct State {
+    values: Vec<Signal>,
+    pending: Vec<bool>,
+    primed: bool,
+    drives: Vec<Vec<Signal>>,
+    fighting: Vec<u64>,
 }
 
 pub enum Element {
     Gate(Gate),
     Complex(Complex),
+    Component(Box<dyn Component>),
 }
 
 impl Element {
     pub fn input(&self) -> Vec<Wire> {
         match self {
-            Element::Gate(gate) => vec![gate.get_in_1(), gate.get_in_2()],
+            Element::Gate(gate) => gate.inputs().to_vec(),
             Element::Complex(complex) => complex.input.clone(),
+            Element::Component(component) => component.input(),
         }
     }
 
@@ -31,15 +155,158 @@ impl Element {
         match self {
             Element::Gate(gate) => vec![gate.get_out()],
             Element::Complex(complex) => complex.output.clone(),
+            Element::Component(component) => component.output(),
+        }
+    }
+
+    pub fn tp(&self) -> &'static str {
+        match self {
+            Element::Gate(gate) => gate.tp(),
+            Element::Complex(complex) => complex.tp(),
+            Element::Component(component) => component.tp(),
+        }
+    }
+
+    fn instantiate(&self, wires: &mut WireMap) -> Element {
+        match self {
+            Element::Gate(gate) => {
+                let mut gate = gate.clone();
+                for index in 0..gate.inputs().len() {
+                    gate.wire_in(index, wires.get(&gate.get_in(index)));
+                }
+                gate.wire_out(wires.get(&gate.get_out()));
+                Element::Gate(gate)
+            }
+            Element::Complex(complex) => Element::Complex(complex.instantiate_with(wires)),
+            Element::Component(component) => {
+                let mut copy = component.box_instantiate();
+                for (port, wire) in component.input().iter().enumerate() {
+                    copy.wire_input(port, wires.get(wire))
+                        .expect("component accepts the inputs it lists");
+                }
+                for (port, wire) in component.output().iter().enumerate() {
+                    copy.wire_output(port, wires.get(wire))
+                        .expect("component accepts the outputs it lists");
+                }
+                Element::Component(copy)
+            }
+        }
+    }
+
+    /// Connects the inputs attached to `old` to `wire` instead, returning whether any matched.
+    pub(crate) fn rewire_input(&mut self, old: WireId, wire: &Wire, compile: bool) -> bool {
+        match self {
+            Element::Gate(gate) => {
+                let mut found = false;
+                for index in 0..gate.inputs().len() {
+                    if gate.get_in(index).id() == old {
+                        gate.wire_in(index, wire.clone());
+                        found = true;
+                    }
+                }
+                found
+            }
+            Element::Component(component) => {
+                let mut found = false;
+                for (port, input) in component.input().iter().enumerate() {
+                    if input.id() == old {
+                        found |= component.wire_input(port, wire.clone()).is_ok();
+                    }
+                }
+                found
+            }
+            Element::Complex(complex) => {
+                let idx = complex
+                    .input
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, w)| w.id() == old)
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+
+                for id in idx.iter() {
+                    complex.rewire_in(*id, wire.clone(), compile);
+                }
+                if compile && !idx.is_empty() {
+                    complex.compile();
+                }
+                !idx.is_empty()
+            }
+        }
+    }
+
+    /// Connects the outputs attached to `old` to `wire` instead, returning whether any matched.
+    pub(crate) fn rewire_output(&mut self, old: WireId, wire: &Wire, compile: bool) -> bool {
+        match self {
+            Element::Gate(gate) => {
+                if gate.get_out().id() == old {
+                    gate.wire_out(wire.clone());
+                    true
+                } else {
+                    false
+                }
+            }
+            Element::Component(component) => {
+                let mut found = false;
+                for (port, output) in component.output().iter().enumerate() {
+                    if output.id() == old {
+                        found |= component.wire_output(port, wire.clone()).is_ok();
+                    }
+                }
+                found
+            }
+            Element::Complex(complex) => {
+                let idx = complex
+                    .output
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, w)| w.id() == old)
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+
+                for id in idx.iter() {
+                    complex.rewire_out(*id, wire.clone(), compile);
+                }
+                if compile && !idx.is_empty() {
+                    complex.compile();
+                }
+                !idx.is_empty()
+            }
         }
     }
 }
 
+impl Element {
+    /// Returns, per input, the outputs that depend on it.
+    fn reach(&self) -> Vec<Vec<usize>> {
+        match self {
+            Element::Gate(gate) => vec![vec![0]; gate.inputs().len()],
+            Element::Complex(complex) => complex.nets.reach.clone(),
+            Element::Component(component) => {
+                vec![(0..component.output().len()).collect(); component.input().len()]
+            }
+        }
+    }
+
+    /// Evaluates the element within a tick of its parent, leaving out stateful components,
+    /// and reports whether it has no evaluations left pending.
+    fn evaluate(&self) -> bool {
+        match self {
+            Element::Gate(gate) => gate.conduct(),
+            Element::Complex(complex) => return complex.tick(),
+            Element::Component(component) if component.is_combinational() => component.conduct(),
+            Element::Component(_) => {}
+        }
+        true
+    }
+}
+
 impl Conduct for Element {
     fn conduct(&self) {
         match self {
             Element::Gate(el) => el.conduct(),
             Element::Complex(el) => el.conduct(),
+            Element::Component(el) => el.conduct(),
         }
     }
 }
@@ -49,9 +316,46 @@ impl Complex {
         Self {
             input: Vec::new(),
             output: Vec::new(),
+            ports: Box::default(),
             gates: Vec::new(),
+            ids: Vec::new(),
+            next_id: 0,
             tp,
             iters_per_tick: 1,
+            settle_limit: DEFAULT_SETTLE_LIMIT,
+            mode: Mode::Iterative,
+            nets: Box::default(),
+            state: Box::default(),
+            observers: Box::default(),
+        }
+    }
+
+    pub fn builder(tp: &'static str) -> ComplexBuilder {
+        ComplexBuilder::new(tp)
+    }
+
+    pub fn tp(&self) -> &'static str {
+        self.tp
+    }
+
+    pub fn settle_limit(&self) -> usize {
+        self.settle_limit
+    }
+
+    pub fn set_settle_limit(&mut self, limit: usize) {
+        self.settle_limit = limit.max(1);
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        for gate in self.gates.iter_mut() {
+            if let Element::Complex(complex) = gate {
+                complex.set_mode(mode);
+            }
         }
     }
 
@@ -79,22 +383,337 @@ impl Complex {
         }
     }
 
-    pub fn add_gate(&mut self, key: Gate) -> usize {
-        let id = self.gates.len();
-        self.gates.push(Element::Gate(key));
+    pub fn add_named_input(
+        &mut self,
+        name: &'static str,
+        wire: Wire,
+    ) -> Result<usize, BinariiError> {
+        self.add_named_input_bus(name, Bus::with_wires(vec![wire]))?;
+        Ok(self.input.len() - 1)
+    }
+
+    /// Adds the wires of `bus` as inputs reachable through `get_in_bus_by_name(name)`, failing
+    /// when the bus is empty or another input port has the same name.
+    pub fn add_named_input_bus(
+        &mut self,
+        name: &'static str,
+        bus: Bus,
+    ) -> Result<(), BinariiError> {
+        self.check_port(&self.ports.input, name, &bus)?;
+        self.ports.input.push(Port {
+            name,
+            offset: self.input.len(),
+            width: bus.size(),
+            order: bus.order(),
+        });
+        self.add_input_bus(bus);
+        Ok(())
+    }
+
+    pub fn add_named_output(
+        &mut self,
+        name: &'static str,
+        wire: Wire,
+    ) -> Result<usize, BinariiError> {
+        self.add_named_output_bus(name, Bus::with_wires(vec![wire]))?;
+        Ok(self.output.len() - 1)
+    }
+
+    /// Adds the wires of `bus` as outputs reachable through `get_out_bus_by_name(name)`,
+    /// failing when the bus is empty or another output port has the same name.
+    pub fn add_named_output_bus(
+        &mut self,
+        name: &'static str,
+        bus: Bus,
+    ) -> Result<(), BinariiError> {
+        self.check_port(&self.ports.output, name, &bus)?;
+        self.ports.output.push(Port {
+            name,
+            offset: self.output.len(),
+            width: bus.size(),
+            order: bus.order(),
+        });
+        self.add_output_bus(bus);
+        Ok(())
+    }
+
+    fn check_port(
+        &self,
+        ports: &[Port],
+        name: &'static str,
+        bus: &Bus,
+    ) -> Result<(), BinariiError> {
+        let reason = if bus.size() == 0 {
+            "no wires"
+        } else if Self::port(ports, name).is_some() {
+            "duplicate name"
+        } else {
+            return Ok(());
+        };
+        Err(BinariiError::InvalidPort {
+            complex: self.tp,
+            name,
+            reason,
+        })
+    }
+
+    pub fn input_ports(&self) -> &[Port] {
+        &self.ports.input
+    }
+
+    pub fn output_ports(&self) -> &[Port] {
+        &self.ports.output
+    }
+
+    /// Names the ports like those of `other`, which has the same port layout.
+    pub(crate) fn name_ports_like(&mut self, other: &Complex) {
+        self.ports = other.ports.clone();
+    }
+
+    fn port(ports: &[Port], name: &str) -> Option<Port> {
+        ports.iter().find(|port| port.name == name).copied()
+    }
+
+    pub fn add_gate(&mut self, key: Gate) -> ElementId {
+        let id = self.push(Element::Gate(key));
+        self.compile();
+        id
+    }
+
+    pub fn add_complex(&mut self, complex: Complex) -> ElementId {
+        let id = self.push(Element::Complex(complex));
         self.compile();
         id
     }
 
-    pub fn add_complex(&mut self, complex: Complex) -> usize {
-        let id = self.gates.len();
-        self.gates.push(Element::Complex(complex));
+    pub fn add_component(&mut self, component: impl Component + 'static) -> ElementId {
+        let id = self.push(Element::Component(Box::new(component)));
         self.compile();
         id
     }
 
-    pub fn get_element(&mut self, id: usize) -> &mut Element {
-        &mut self.gates[id]
+    pub fn add_element(&mut self, element: Element) -> ElementId {
+        let id = self.push(element);
+        self.compile();
+        id
+    }
+
+    pub(crate) fn push(&mut self, element: Element) -> ElementId {
+        let id = ElementId(self.next_id);
+        self.next_id += 1;
+        self.gates.push(element);
+        self.ids.push(id);
+        id
+    }
+
+    fn position(&self, id: ElementId) -> Option<usize> {
+        self.ids.iter().position(|i| *i == id)
+    }
+
+    /// Returns the element handles in compiled order.
+    pub fn element_ids(&self) -> &[ElementId] {
+        &self.ids
+    }
+
+    /// Iterates over the elements and their handles in compiled order.
+    pub fn elements(&self) -> impl Iterator<Item = (ElementId, &Element)> {
+        self.ids.iter().copied().zip(self.gates.iter())
+    }
+
+    pub fn element(&self, id: ElementId) -> Option<&Element> {
+        self.position(id).map(|idx| &self.gates[idx])
+    }
+
+    /// Gives mutable access to an element; call `compile` after changing its wiring.
+    pub fn get_element(&mut self, id: ElementId) -> Option<&mut Element> {
+        self.position(id).map(|idx| &mut self.gates[idx])
+    }
+
+    pub fn remove_element(&mut self, id: ElementId) -> Option<Element> {
+        let idx = self.position(id)?;
+        self.ids.remove(idx);
+        let element = self.gates.remove(idx);
+        self.compile();
+        Some(element)
+    }
+
+    /// Puts `element` in place of the element `id`, keeping the handle, and returns the old one.
+    pub fn replace_element(&mut self, id: ElementId, element: Element) -> Option<Element> {
+        let idx = self.position(id)?;
+        let old = mem::replace(&mut self.gates[idx], element);
+        self.compile();
+        Some(old)
+    }
+
+    /// Detaches the ports of element `id` connected to `wire`, leaving them on fresh wires.
+    /// Returns whether any port was connected to `wire`.
+    pub fn disconnect(&mut self, id: ElementId, wire: &Wire) -> bool {
+        let idx = if let Some(idx) = self.position(id) {
+            idx
+        } else {
+            return false;
+        };
+
+        let element = &mut self.gates[idx];
+        let input = element.rewire_input(wire.id(), &Wire::new(), true);
+        let output = element.rewire_output(wire.id(), &Wire::new(), true);
+        if input || output {
+            self.compile();
+        }
+        input || output
+    }
+
+    /// Deep-copies the hierarchy onto fresh wires, so the copy simulates independently.
+    ///
+    /// Wires shared between elements stay shared within the copy and start with the same signals.
+    pub fn instantiate(&self) -> Complex {
+        self.instantiate_with(&mut WireMap::default())
+    }
+
+    fn instantiate_with(&self, wires: &mut WireMap) -> Complex {
+        let mut complex = Complex {
+            input: self.input.iter().map(|wire| wires.get(wire)).collect(),
+            output: self.output.iter().map(|wire| wires.get(wire)).collect(),
+            ports: self.ports.clone(),
+            gates: self
+                .gates
+                .iter()
+                .map(|gate| gate.instantiate(wires))
+                .collect(),
+            ids: self.ids.clone(),
+            next_id: self.next_id,
+            tp: self.tp,
+            iters_per_tick: self.iters_per_tick,
+            settle_limit: self.settle_limit,
+            mode: self.mode,
+            nets: Box::default(),
+            state: Box::default(),
+            observers: Box::default(),
+        };
+        complex.index_nets();
+        complex
+    }
+
+    /// Names every wire of the hierarchy by its label, or by its number in `nets` as `n{k}`.
+    pub(crate) fn net_names(&self) -> HashMap<WireId, String> {
+        self.nets()
+            .iter()
+            .enumerate()
+            .map(|(net, wire)| (wire.id(), wire.label().unwrap_or(format!("n{}", net))))
+            .collect()
+    }
+
+    /// Returns every wire of the hierarchy once, numbered by first appearance: the ports of
+    /// this complex, then the wires of each element in compiled order, depth first.
+    pub fn nets(&self) -> Vec<Wire> {
+        let mut seen = HashSet::new();
+        let mut nets = Vec::new();
+        self.collect_nets(&mut seen, &mut nets);
+        nets
+    }
+
+    fn collect_nets(&self, seen: &mut HashSet<WireId>, nets: &mut Vec<Wire>) {
+        for wire in self.input.iter().chain(&self.output) {
+            if seen.insert(wire.id()) {
+                nets.push(wire.clone());
+            }
+        }
+        for element in &self.gates {
+            match element {
+                Element::Complex(complex) => complex.collect_nets(seen, nets),
+                _ => {
+                    for wire in element.input().into_iter().chain(element.output()) {
+                        if seen.insert(wire.id()) {
+                            nets.push(wire);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns a single-level copy of the hierarchy; see `Netlist`.
+    pub fn flatten(&self) -> Complex {
+        Netlist::new(self).into_complex()
+    }
+
+    /// Calls `callback` at the end of every tick that changed `wire`.
+    ///
+    /// Observers of a sub-complex fire once per tick of the top-level complex.
+    pub fn observe(
+        &mut self,
+        wire: &Wire,
+        callback: impl FnMut(&WireChange) + Send + 'static,
+    ) -> ObserverId {
+        self.observers
+            .get_mut()
+            .add(std::slice::from_ref(wire), callback)
+    }
+
+    /// Calls `callback` once per changed wire of `bus` at the end of every tick.
+    pub fn observe_bus(
+        &mut self,
+        bus: &Bus,
+        callback: impl FnMut(&WireChange) + Send + 'static,
+    ) -> ObserverId {
+        self.observers.get_mut().add(bus.wires(), callback)
+    }
+
+    pub fn unobserve(&mut self, id: ObserverId) -> bool {
+        self.observers.get_mut().remove(id)
+    }
+
+    /// Returns the number of `conduct` and `settle` calls on the top-level complex so far.
+    pub fn ticks(&self) -> u64 {
+        self.observers.borrow().tick()
+    }
+
+    pub fn input_len(&self) -> usize {
+        self.input.len()
+    }
+
+    pub fn output_len(&self) -> usize {
+        self.output.len()
+    }
+
+    /// Compiles the hierarchy into a `Program`, failing when it holds a component.
+    pub fn program(&self) -> Result<Program, BinariiError> {
+        Program::new(self)
+    }
+
+    /// Fails with `UnsupportedComponent` when the hierarchy holds a component, which the
+    /// gate-only models `model` cannot drive.
+    pub(crate) fn check_gates_only(&self, model: &'static str) -> Result<(), BinariiError> {
+        let mut component = None;
+        self.for_each_component(&mut |found| {
+            component.get_or_insert(found.tp());
+        });
+        match component {
+            Some(component) => Err(BinariiError::UnsupportedComponent { model, component }),
+            None => Ok(()),
+        }
+    }
+
+    /// Visits every gate of the hierarchy in compiled order.
+    pub(crate) fn for_each_gate(&self, f: &mut impl FnMut(&Gate)) {
+        for gate in self.gates.iter() {
+            match gate {
+                Element::Gate(gate) => f(gate),
+                Element::Complex(complex) => complex.for_each_gate(f),
+                Element::Component(_) => {}
+            }
+        }
+    }
+
+    /// Sets the propagation delay of every gate in the hierarchy.
+    pub fn set_delay(&mut self, delay: u32) {
+        for gate in self.gates.iter_mut() {
+            match gate {
+                Element::Gate(gate) => gate.set_delay(delay),
+                Element::Complex(complex) => complex.set_delay(delay),
+                Element::Component(_) => {}
+            }
+        }
     }
 
     pub fn get_in(&self, wire_id: usize) -> Wire {
@@ -110,69 +729,150 @@ impl Complex {
     }
 
     pub fn set_in(&mut self, wire_id: usize, wire: Wire) {
-        for gate in self.gates.iter_mut() {
-            match gate {
-                Element::Gate(gate) => {
-                    if gate.get_in_1().id() == self.input[wire_id].id() {
-                        gate.wire_in_1(wire.clone());
-                    }
-                    if gate.get_in_2().id() == self.input[wire_id].id() {
-                        gate.wire_in_2(wire.clone());
-                    }
-                }
-                Element::Complex(complex) => {
-                    let idx = complex
-                        .input
-                        .iter_mut()
-                        .enumerate()
-                        .filter(|(_, w)| w.id() == self.input[wire_id].id())
-                        .map(|(i, _)| i)
-                        .collect::<Vec<_>>();
-
-                    for id in idx {
-                        complex.set_in(id, wire.clone());
-                    }
-                }
-            }
+        self.rewire_in(wire_id, wire, true);
+        self.compile();
+    }
+
+    pub fn try_get_in(&self, wire_id: usize) -> Result<Wire, BinariiError> {
+        self.check_in(wire_id, 1)?;
+        Ok(self.get_in(wire_id))
+    }
+
+    pub fn try_get_in_bus(&self, offset: usize, len: usize) -> Result<Bus, BinariiError> {
+        self.check_in(offset, len)?;
+        Ok(self.get_in_bus(offset, len))
+    }
+
+    /// Like `set_in`, but fails instead of only renaming the port when no element reads
+    /// input `wire_id`.
+    pub fn try_set_in(&mut self, wire_id: usize, wire: Wire) -> Result<(), BinariiError> {
+        self.check_in(wire_id, 1)?;
+        let old = self.input[wire_id].id();
+        if !self
+            .gates
+            .iter()
+            .any(|g| g.input().iter().any(|w| w.id() == old))
+        {
+            return Err(BinariiError::UnconnectedWire {
+                complex: self.tp,
+                wire: old,
+            });
         }
+        self.set_in(wire_id, wire);
+        Ok(())
+    }
 
+    fn check_in(&self, offset: usize, len: usize) -> Result<(), BinariiError> {
+        if offset
+            .checked_add(len)
+            .is_none_or(|end| end > self.input.len())
+        {
+            return Err(BinariiError::InputOutOfRange {
+                element: self.tp,
+                index: offset.saturating_add(len) - 1,
+                len: self.input.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Replaces input `wire_id` in the hierarchy, compiling the rewired sub-complexes when
+    /// `compile` is set.
+    pub(crate) fn rewire_in(&mut self, wire_id: usize, wire: Wire, compile: bool) {
+        let old = self.input[wire_id].id();
+        for gate in self.gates.iter_mut() {
+            gate.rewire_input(old, &wire, compile);
+        }
         self.input[wire_id] = wire;
-        self.compile();
     }
 
     pub fn get_out(&self, wire_id: usize) -> Wire {
         self.output[wire_id].clone()
     }
 
+    /// Returns the first wire of the input port `name`.
+    pub fn get_in_by_name(&self, name: &str) -> Option<Wire> {
+        Self::port(&self.ports.input, name).map(|port| self.get_in(port.offset))
+    }
+
+    pub fn get_in_bus_by_name(&self, name: &str) -> Option<Bus> {
+        Self::port(&self.ports.input, name).map(|port| {
+            self.get_in_bus(port.offset, port.width)
+                .with_order(port.order)
+        })
+    }
+
+    /// Returns the first wire of the output port `name`.
+    pub fn get_out_by_name(&self, name: &str) -> Option<Wire> {
+        Self::port(&self.ports.output, name).map(|port| self.get_out(port.offset))
+    }
+
+    pub fn get_out_bus_by_name(&self, name: &str) -> Option<Bus> {
+        Self::port(&self.ports.output, name).map(|port| {
+            self.get_out_bus(port.offset, port.width)
+                .with_order(port.order)
+        })
+    }
+
     pub fn set_out(&mut self, wire_id: usize, wire: Wire) {
-        for gate in self.gates.iter_mut() {
-            match gate {
-                Element::Gate(gate) => {
-                    if gate.get_out().id() == self.output[wire_id].id() {
-                        gate.wire_out(wire.clone());
-                    }
-                }
-                Element::Complex(complex) => {
-                    let idx = complex
-                        .output
-                        .iter_mut()
-                        .enumerate()
-                        .filter(|(_, w)| w.id() == self.output[wire_id].id())
-                        .map(|(i, _)| i)
-                        .collect::<Vec<_>>();
-
-                    for id in idx {
-                        complex.set_out(id, wire.clone());
-                    }
-                }
-            }
+        self.rewire_out(wire_id, wire, true);
+        self.compile();
+    }
+
+    pub fn try_get_out(&self, wire_id: usize) -> Result<Wire, BinariiError> {
+        self.check_out(wire_id, 1)?;
+        Ok(self.get_out(wire_id))
+    }
+
+    pub fn try_get_out_bus(&self, offset: usize, len: usize) -> Result<Bus, BinariiError> {
+        self.check_out(offset, len)?;
+        Ok(self.get_out_bus(offset, len))
+    }
+
+    /// Like `set_out`, but fails instead of only renaming the port when no element drives
+    /// output `wire_id`.
+    pub fn try_set_out(&mut self, wire_id: usize, wire: Wire) -> Result<(), BinariiError> {
+        self.check_out(wire_id, 1)?;
+        let old = self.output[wire_id].id();
+        if !self
+            .gates
+            .iter()
+            .any(|g| g.output().iter().any(|w| w.id() == old))
+        {
+            return Err(BinariiError::UnconnectedWire {
+                complex: self.tp,
+                wire: old,
+            });
         }
+        self.set_out(wire_id, wire);
+        Ok(())
+    }
 
+    fn check_out(&self, offset: usize, len: usize) -> Result<(), BinariiError> {
+        if offset
+            .checked_add(len)
+            .is_none_or(|end| end > self.output.len())
+        {
+            return Err(BinariiError::OutputOutOfRange {
+                element: self.tp,
+                index: offset.saturating_add(len) - 1,
+                len: self.output.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Replaces output `wire_id` in the hierarchy, compiling the rewired sub-complexes when
+    /// `compile` is set.
+    pub(crate) fn rewire_out(&mut self, wire_id: usize, wire: Wire, compile: bool) {
+        let old = self.output[wire_id].id();
+        for gate in self.gates.iter_mut() {
+            gate.rewire_output(old, &wire, compile);
+        }
         self.output[wire_id] = wire;
-        self.compile();
     }
 
-    pub fn compile(&mut self) {
+    pub fn compile(&mut self) -> Analysis {
         self.iters_per_tick = 1;
         let mut gates = Vec::new();
         let mut mapping = mem::take(&mut self.gates)
@@ -180,16 +880,178 @@ impl Complex {
             .enumerate()
             .collect::<HashMap<usize, Element>>();
 
-        while let Some(key) = mapping.keys().next().cloned() {
-            self.map(&mut gates, &mut mapping, key);
+        let mut drivers = HashMap::<WireId, Vec<usize>>::new();
+        for (key, element) in mapping.iter() {
+            for wire in element.output() {
+                drivers.entry(wire.id()).or_default().push(*key);
+            }
+        }
+        let inputs = self.input.iter().map(|w| w.id()).collect::<HashSet<_>>();
+
+        let mut placed = vec![false; mapping.len()];
+        for key in 0..placed.len() {
+            self.map(
+                &mut gates,
+                &mut mapping,
+                &mut placed,
+                &drivers,
+                &inputs,
+                key,
+            );
+        }
+        let ids = mem::take(&mut self.ids);
+        self.ids = gates.iter().map(|(key, _)| ids[*key]).collect();
+        self.gates = gates.into_iter().map(|(_, element)| element).collect();
+        self.index_nets();
+        self.analysis()
+    }
+
+    pub fn analysis(&self) -> Analysis {
+        let mut analysis = Analysis::default();
+        let names = if self.nets.cycles.is_empty() {
+            HashMap::new()
+        } else {
+            self.net_names()
+        };
+        for (cycle, nets) in self.nets.cycles.iter() {
+            let wires: Vec<Wire> = nets
+                .iter()
+                .map(|&net| self.nets.wires[net].clone())
+                .collect();
+
+            analysis.feedback.push(Feedback {
+                path: vec![self.tp],
+                elements: cycle.iter().map(|&idx| self.ids[idx]).collect(),
+                kinds: cycle.iter().map(|&idx| self.gates[idx].tp()).collect(),
+                names: wires.iter().map(|wire| names[&wire.id()].clone()).collect(),
+                wires,
+            });
+        }
+
+        for gate in self.gates.iter() {
+            if let Element::Complex(complex) = gate {
+                for mut feedback in complex.analysis().feedback {
+                    feedback.path.insert(0, self.tp);
+                    analysis.feedback.push(feedback);
+                }
+            }
+        }
+        analysis
+    }
+
+    fn index_nets(&mut self) {
+        let mut nets = Nets::default();
+        let mut ids = HashMap::new();
+        let mut net = |wire: Wire, nets: &mut Nets| {
+            *ids.entry(wire.id()).or_insert_with(|| {
+                nets.wires.push(wire);
+                nets.fanout.push(Vec::new());
+                nets.drivers.push(Vec::new());
+                nets.wires.len() - 1
+            })
+        };
+
+        for (idx, gate) in self.gates.iter().enumerate() {
+            for wire in gate.input() {
+                let id = net(wire, &mut nets);
+                nets.fanout[id].push(idx);
+            }
+            let mut outputs = Vec::new();
+            for wire in gate.output() {
+                let id = net(wire, &mut nets);
+                nets.drivers[id].push(idx);
+                outputs.push(id);
+            }
+            nets.outputs.push(outputs);
+        }
+
+        // Loops are searched between nets, following only the inputs each output depends on,
+        // so a sub-complex is not taken for a loop when it passes separate signals through.
+        let mut successors = vec![Vec::new(); nets.wires.len()];
+        let mut edges = Vec::new();
+        for (idx, gate) in self.gates.iter().enumerate() {
+            for (input, outputs) in gate.input().iter().zip(gate.reach()) {
+                let from = ids[&input.id()];
+                for output in outputs {
+                    let to = nets.outputs[idx][output];
+                    successors[from].push(to);
+                    edges.push((idx, from, to));
+                }
+            }
         }
-        self.gates = gates;
+        nets.cycles = analysis::cycles(&successors)
+            .into_iter()
+            .map(|cycle| {
+                let mut elements = edges
+                    .iter()
+                    .filter(|(_, from, to)| cycle.contains(from) && cycle.contains(to))
+                    .map(|(idx, _, _)| *idx)
+                    .collect::<Vec<_>>();
+                elements.dedup();
+                (elements, cycle)
+            })
+            .collect();
+
+        nets.reach = self
+            .input
+            .iter()
+            .map(|input| {
+                let mut reached = vec![false; nets.wires.len()];
+                let mut queue = ids
+                    .get(&input.id())
+                    .copied()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                while let Some(net) = queue.pop() {
+                    if !reached[net] {
+                        reached[net] = true;
+                        queue.extend(successors[net].iter().copied());
+                    }
+                }
+                self.output
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, output)| ids.get(&output.id()).is_some_and(|&net| reached[net]))
+                    .map(|(port, _)| port)
+                    .collect()
+            })
+            .collect();
+
+        nets.clocked = self
+            .gates
+            .iter()
+            .map(|gate| match gate {
+                Element::Gate(_) => false,
+                Element::Complex(complex) => complex.is_clocked(),
+                Element::Component(component) => !component.is_combinational(),
+            })
+            .collect();
+
+        nets.drives = vec![Vec::new(); self.gates.len()];
+        for (net, drivers) in nets.drivers.iter().enumerate() {
+            if drivers.len() > 1 {
+                for (slot, &idx) in drivers.iter().enumerate() {
+                    nets.drives[idx].push((nets.shared.len(), slot));
+                }
+                nets.shared.push(net);
+            }
+        }
+
+        *self.state = RefCell::new(nets.state());
+        *self.nets = nets;
     }
 
+    /// Places the element at `key` after the elements driving its inputs.
+    ///
+    /// Every input that is not a port and has no driver left to place, because its driver is
+    /// already placed, still being placed or missing, costs an extra iteration per tick.
     fn map(
         &mut self,
-        gates: &mut Vec<Element>,
+        gates: &mut Vec<(usize, Element)>,
         elements: &mut HashMap<usize, Element>,
+        placed: &mut [bool],
+        drivers: &HashMap<WireId, Vec<usize>>,
+        inputs: &HashSet<WireId>,
         key: usize,
     ) {
         let element = if let Some(element) = elements.remove(&key) {
@@ -199,89 +1061,451 @@ impl Complex {
         };
 
         let input = element.input();
+        let mut stack = vec![(key, element, input, 0)];
+        while let Some((_, _, input, next)) = stack.last_mut() {
+            let wire = if let Some(wire) = input.get(*next) {
+                *next += 1;
+                wire.id()
+            } else {
+                let (key, element, _, _) = stack.pop().unwrap();
+                placed[key] = true;
+                gates.push((key, element));
+                continue;
+            };
 
-        for wire in input.iter() {
-            if self.input.iter().any(|w| w.id() == wire.id()) {
+            if inputs.contains(&wire) {
                 continue;
             }
 
-            let element_to_handle = elements.iter().find_map(|(key, wal)| {
-                wal.output().iter().find_map(|w| {
-                    if w.id() == wire.id() {
-                        Some(*key)
-                    } else {
-                        None
-                    }
-                })
-            });
+            let keys = drivers
+                .get(&wire)
+                .map(|keys| keys.as_slice())
+                .unwrap_or(&[]);
+            let element_to_handle = keys.iter().find(|key| elements.contains_key(key));
 
-            if let Some(key) = element_to_handle {
-                self.map(gates, elements, key);
-            } else {
+            if let Some(&key) = element_to_handle {
+                let element = elements.remove(&key).unwrap();
+                let input = element.input();
+                stack.push((key, element, input, 0));
+            } else if keys.is_empty() || keys.iter().any(|key| !placed[*key]) {
+                // The input is undriven or closes a loop, so it lags one pass behind.
                 self.iters_per_tick += 1;
             }
         }
+    }
 
-        gates.push(element);
+    /// Compiles every sub-complex of the hierarchy and then this complex.
+    pub(crate) fn compile_deep(&mut self) -> Analysis {
+        for gate in self.gates.iter_mut() {
+            if let Element::Complex(complex) = gate {
+                complex.compile_deep();
+            }
+        }
+        self.compile()
     }
 }
 
-impl Conduct for Complex {
-    fn conduct(&self) {
-        for _ in 0..self.iters_per_tick {
-            for gate in self.gates.iter() {
-                gate.conduct();
+impl Complex {
+    /// Runs one tick and reports whether the complex has no evaluations left pending.
+    ///
+    /// Stateful components are left out; `step_components` conducts them once beforehand.
+    fn tick(&self) -> bool {
+        match self.mode {
+            Mode::Iterative if self.nets.shared.is_empty() => {
+                for _ in 0..self.iters_per_tick {
+                    for gate in self.gates.iter() {
+                        gate.evaluate();
+                    }
+                }
+                true
+            }
+            Mode::Iterative => {
+                let mut state = self.state.borrow_mut();
+                for _ in 0..self.iters_per_tick {
+                    for (idx, gate) in self.gates.iter().enumerate() {
+                        gate.evaluate();
+                        self.resolve(&mut state, idx);
+                    }
+                }
+                true
             }
+            Mode::EventDriven => self.tick_events(),
         }
     }
-}
 
-impl Debug for Complex {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "[")?;
-        for gate in &self.gates {
-            writeln!(f, "{:?}", gate)?;
+    /// Conducts every stateful component of the hierarchy once.
+    fn step_components(&self) {
+        for gate in self.gates.iter() {
+            match gate {
+                Element::Component(component) if !component.is_combinational() => {
+                    component.conduct()
+                }
+                Element::Complex(complex) => complex.step_components(),
+                _ => {}
+            }
         }
-        writeln!(f, "]")?;
+    }
 
-        for i in &self.input {
-            writeln!(f, "IN:{:#x}", i.id())?;
+    /// Whether the hierarchy holds a stateful component, so it changes on every tick.
+    fn is_clocked(&self) -> bool {
+        self.nets.clocked.iter().any(|clocked| *clocked)
+    }
+
+    /// Combines the drivers of the shared nets written by the element at `idx`.
+    fn resolve(&self, state: &mut State, idx: usize) {
+        for &(shared, slot) in self.nets.drives[idx].iter() {
+            let wire = &self.nets.wires[self.nets.shared[shared]];
+            let drives = &mut state.drives[shared];
+            drives[slot] = wire.raw_signal();
+            let (signal, fighting) = Signal::resolve(drives);
+            wire.set_signal(signal);
+            state.fighting[shared] = fighting;
         }
-        for i in &self.output {
-            writeln!(f, "OUT:{:#x}", i.id())?;
+    }
+
+    /// Puts the hierarchy into an uninitialized state: every driven wire becomes `X` and every
+    /// wire without a driver, such as the inputs, floats at `Z` or its pull until it is set.
+    pub fn set_unknown(&self) {
+        for gate in self.gates.iter() {
+            if let Element::Complex(complex) = gate {
+                complex.set_unknown();
+            }
         }
-        writeln!(f, "ITERS:{}", self.iters_per_tick)?;
-        write!(f, "{}(", self.tp)?;
-        for i in &self.input {
-            write!(f, "{},", i)?;
+        for (net, wire) in self.nets.wires.iter().enumerate() {
+            if self.nets.drivers[net].is_empty() {
+                wire.set_signal(Signal::Z);
+            } else {
+                wire.set_signal(Signal::X);
+            }
+        }
+    }
+
+    /// Captures the signals of every wire and the state of every component in the hierarchy.
+    pub fn snapshot(&self) -> Snapshot {
+        let mut components = Vec::new();
+        self.for_each_component(&mut |component| components.push(component.save()));
+        Snapshot {
+            signals: self.nets().iter().map(|wire| wire.raw_signal()).collect(),
+            components,
+        }
+    }
+
+    /// Returns the hierarchy to the values of `snapshot`, taken from this complex or from an
+    /// instance of the same template.
+    pub fn restore(&self, snapshot: &Snapshot) -> Result<(), BinariiError> {
+        let nets = self.nets();
+        let mut components = 0;
+        self.for_each_component(&mut |_| components += 1);
+        if nets.len() != snapshot.signals.len() || components != snapshot.components.len() {
+            return Err(BinariiError::InvalidSnapshot(
+                "snapshot does not match the circuit",
+            ));
+        }
+
+        // Components are loaded before the wires are touched and put back on a failure, so
+        // a rejected snapshot leaves the circuit as it was.
+        let mut saved = Vec::new();
+        self.for_each_component(&mut |component| saved.push(component.save()));
+        let mut states = snapshot.components.iter();
+        let mut loaded = Ok(());
+        self.for_each_component(&mut |component| {
+            let state = states.next().unwrap();
+            if loaded.is_ok() {
+                loaded = component.load(state);
+            }
+        });
+        if loaded.is_err() {
+            let mut saved = saved.iter();
+            self.for_each_component(&mut |component| {
+                component
+                    .load(saved.next().unwrap())
+                    .expect("component loads the state it saved")
+            });
+            return loaded;
+        }
+
+        for (wire, signal) in nets.iter().zip(&snapshot.signals) {
+            wire.set_signal(*signal);
+        }
+        self.reset_state();
+        Ok(())
+    }
+
+    fn for_each_component(&self, f: &mut impl FnMut(&dyn Component)) {
+        for gate in self.gates.iter() {
+            match gate {
+                Element::Component(component) => f(component.as_ref()),
+                Element::Complex(complex) => complex.for_each_component(f),
+                Element::Gate(_) => {}
+            }
+        }
+    }
+
+    /// Forgets the event-driven and multi-driver state so it is rebuilt from the wires.
+    fn reset_state(&self) {
+        *self.state.borrow_mut() = self.nets.state();
+        for gate in self.gates.iter() {
+            if let Element::Complex(complex) = gate {
+                complex.reset_state();
+            }
+        }
+    }
+
+    /// Returns the wires of the hierarchy whose drivers currently fight.
+    pub fn contention(&self) -> Vec<Wire> {
+        let state = self.state.borrow();
+        let mut wires = self
+            .nets
+            .shared
+            .iter()
+            .zip(state.fighting.iter())
+            .filter(|(_, fighting)| **fighting != 0)
+            .map(|(&net, _)| self.nets.wires[net].clone())
+            .collect::<Vec<_>>();
+        for gate in self.gates.iter() {
+            if let Element::Complex(complex) = gate {
+                wires.extend(complex.contention());
+            }
         }
+        wires
+    }
+
+    fn tick_events(&self) -> bool {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+
+        if !state.primed {
+            state.values = self.nets.wires.iter().map(|w| w.signal()).collect();
+            state.pending = vec![true; self.gates.len()];
+            state.primed = true;
+        }
+
+        for (pending, clocked) in state.pending.iter_mut().zip(self.nets.clocked.iter()) {
+            *pending |= *clocked;
+        }
+
+        for (net, wire) in self.nets.wires.iter().enumerate() {
+            let value = wire.signal();
+            if state.values[net] != value {
+                state.values[net] = value;
+                for &idx in self.nets.fanout[net].iter().chain(&self.nets.drivers[net]) {
+                    state.pending[idx] = true;
+                }
+            }
+        }
+
+        for _ in 0..self.iters_per_tick {
+            let mut evaluated = false;
+            for (idx, gate) in self.gates.iter().enumerate() {
+                if !state.pending[idx] {
+                    continue;
+                }
+                state.pending[idx] = false;
+                evaluated = true;
+
+                if !gate.evaluate() {
+                    state.pending[idx] = true;
+                }
+                if !self.nets.drives[idx].is_empty() {
+                    self.resolve(state, idx);
+                }
+
+                for &net in self.nets.outputs[idx].iter() {
+                    let value = self.nets.wires[net].signal();
+                    if state.values[net] != value {
+                        state.values[net] = value;
+                        for &idx in self.nets.fanout[net].iter() {
+                            state.pending[idx] = true;
+                        }
+                    }
+                }
+            }
+            if !evaluated {
+                break;
+            }
+        }
+
+        !state.pending.iter().any(|p| *p)
+    }
+}
+
+impl Complex {
+    /// Steps the stateful components once, then evaluates all elements until none of the wires
+    /// change, returning the number of passes.
+    pub fn settle(&self) -> Result<usize, Oscillation> {
+        self.step_components();
+        let result = self.fixpoint();
+        self.notify(self.ticks() + 1);
+        result
+    }
+
+    /// Reports the changes of `tick` to the observers of the whole hierarchy.
+    fn notify(&self, tick: u64) {
+        for gate in self.gates.iter() {
+            if let Element::Complex(complex) = gate {
+                complex.notify(tick);
+            }
+        }
+        self.observers.borrow_mut().notify(tick);
+    }
+
+    fn fixpoint(&self) -> Result<usize, Oscillation> {
+        let mut values = self
+            .nets
+            .wires
+            .iter()
+            .map(|w| w.signal())
+            .collect::<Vec<_>>();
+        let mut toggling = Vec::new();
+
+        for iteration in 1..=self.settle_limit {
+            for (idx, gate) in self.gates.iter().enumerate() {
+                match gate {
+                    Element::Complex(complex) => {
+                        complex.fixpoint()?;
+                    }
+                    _ => {
+                        gate.evaluate();
+                    }
+                }
+                self.resolve(&mut self.state.borrow_mut(), idx);
+            }
+
+            toggling.clear();
+            for (net, wire) in self.nets.wires.iter().enumerate() {
+                let value = wire.signal();
+                if values[net] != value {
+                    values[net] = value;
+                    toggling.push(wire.clone());
+                }
+            }
+            if toggling.is_empty() {
+                return Ok(iteration);
+            }
+        }
+
+        let names = self.net_names();
+        Err(Oscillation {
+            complex: self.tp,
+            iterations: self.settle_limit,
+            names: toggling
+                .iter()
+                .map(|wire| names[&wire.id()].clone())
+                .collect(),
+            wires: toggling,
+        })
+    }
+}
+
+impl Conduct for Complex {
+    fn conduct(&self) {
+        self.step_components();
+        self.tick();
+        self.notify(self.ticks() + 1);
+    }
+}
+
+/// Prints the hierarchy as an indented tree of `tp(net=value,..)->(..)` lines, naming wires
+/// by label or by their number in `nets`, so dumps compare equal across runs.
+impl Debug for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_tree(f, &self.net_names(), 0)?;
+        writeln!(f, "ITERS:{}", self.iters_per_tick)
+    }
+}
+
+impl Complex {
+    fn fmt_tree(
+        &self,
+        f: &mut Formatter<'_>,
+        names: &HashMap<WireId, String>,
+        depth: usize,
+    ) -> std::fmt::Result {
+        let indent = "  ".repeat(depth);
+        write!(f, "{}{}(", indent, self.tp)?;
+        fmt_nets(f, &self.input, names)?;
         write!(f, ")->(")?;
-        for i in &self.output {
-            write!(f, "{},", i)?;
+        fmt_nets(f, &self.output, names)?;
+        writeln!(f, ")")?;
+
+        for element in &self.gates {
+            match element {
+                Element::Complex(complex) => complex.fmt_tree(f, names, depth + 1)?,
+                _ => {
+                    write!(f, "{}  {}(", indent, element.tp())?;
+                    fmt_nets(f, &element.input(), names)?;
+                    write!(f, ")->(")?;
+                    fmt_nets(f, &element.output(), names)?;
+                    writeln!(f, ")")?;
+                }
+            }
         }
-        writeln!(f, ")")
+        Ok(())
+    }
+}
+
+fn fmt_nets(
+    f: &mut Formatter<'_>,
+    wires: &[Wire],
+    names: &HashMap<WireId, String>,
+) -> std::fmt::Result {
+    for wire in wires {
+        write!(f, "{}={},", names[&wire.id()], wire)?;
     }
+    Ok(())
 }
 
 impl Display for Complex {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}(", self.tp)?;
-        for i in &self.input {
-            write!(f, "{},", i)?;
-        }
+        fmt_ports(f, &self.input, &self.ports.input)?;
         write!(f, ")->(")?;
-        for i in &self.output {
-            write!(f, "{},", i)?;
-        }
+        fmt_ports(f, &self.output, &self.ports.output)?;
         writeln!(f, ")")
     }
 }
 
+/// Writes the wires of named ports grouped as `name:bits,` and the others one by one.
+fn fmt_ports(f: &mut Formatter<'_>, wires: &[Wire], ports: &[Port]) -> std::fmt::Result {
+    let mut i = 0;
+    while i < wires.len() {
+        match ports.iter().find(|port| port.offset == i && port.width > 0) {
+            Some(port) => {
+                write!(f, "{}:", port.name)?;
+                for wire in &wires[i..i + port.width] {
+                    write!(f, "{}", wire)?;
+                }
+                write!(f, ",")?;
+                i += port.width;
+            }
+            None => {
+                if let Some(label) = wires[i].label() {
+                    write!(f, "{}:", label)?;
+                }
+                write!(f, "{},", wires[i])?;
+                i += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
 impl Display for Element {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Element::Gate(gate) => write!(f, "{}", gate),
             Element::Complex(complex) => write!(f, "{}", complex),
+            Element::Component(component) => {
+                write!(f, "{}(", component.tp())?;
+                for i in component.input() {
+                    write!(f, "{},", i)?;
+                }
+                write!(f, ")->(")?;
+                for i in component.output() {
+                    write!(f, "{},", i)?;
+                }
+                writeln!(f, ")")
+            }
         }
     }
 }
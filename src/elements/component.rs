@@ -0,0 +1,50 @@
+use crate::elements::error::BinariiError;
+use crate::elements::wire::Wire;
+use crate::elements::Conduct;
+
+/// A user-defined part that a `Complex` can hold next to gates and sub-complexes.
+///
+/// Components are opaque to the simulator: they take part in `compile` through their ports.
+/// A `Complex` conducts a stateful component exactly once per tick, before evaluating the
+/// rest of the circuit, and evaluates a combinational one as often as its gates.
+pub trait Component: Conduct + Send {
+    fn tp(&self) -> &'static str;
+
+    fn input(&self) -> Vec<Wire>;
+
+    fn output(&self) -> Vec<Wire>;
+
+    /// Connects input `port` to `wire`, failing when the component has no such input.
+    fn wire_input(&mut self, port: usize, wire: Wire) -> Result<(), BinariiError>;
+
+    fn wire_output(&mut self, port: usize, wire: Wire) -> Result<(), BinariiError>;
+
+    /// Returns a copy that shares internal state with `self`, as `Clone` does.
+    fn box_clone(&self) -> Box<dyn Component>;
+
+    /// Whether `conduct` only derives the outputs from the inputs and keeps no state.
+    fn is_combinational(&self) -> bool {
+        false
+    }
+
+    /// Returns a copy on the same wires with its own internal state, starting from the
+    /// current one.
+    fn box_instantiate(&self) -> Box<dyn Component>;
+
+    /// Encodes the internal state for `Complex::snapshot`.
+    fn save(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// Restores the internal state from the bytes returned by `save`, failing with
+    /// `InvalidSnapshot` when they do not encode one.
+    fn load(&self, _state: &[u8]) -> Result<(), BinariiError> {
+        Ok(())
+    }
+}
+
+impl Clone for Box<dyn Component> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
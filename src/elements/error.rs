@@ -0,0 +1,114 @@
+use crate::elements::complex::Oscillation;
+use crate::elements::signal::WireId;
+use std::error::Error;
+use std::fmt::Display;
+
+#[derive(Debug, Clone)]
+pub enum BinariiError {
+    /// An input port index past the inputs of an element.
+    InputOutOfRange {
+        element: &'static str,
+        index: usize,
+        len: usize,
+    },
+    /// An output port index past the outputs of an element.
+    OutputOutOfRange {
+        element: &'static str,
+        index: usize,
+        len: usize,
+    },
+    /// A wire index past the end of a bus.
+    WireOutOfRange {
+        index: usize,
+        len: usize,
+    },
+    /// A value needs more wires than the bus has from the given offset.
+    WidthMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// A port wire is not connected to any element of the complex.
+    UnconnectedWire {
+        complex: &'static str,
+        wire: WireId,
+    },
+    /// Snapshot bytes that cannot be decoded or do not fit the circuit.
+    InvalidSnapshot(&'static str),
+    /// A truth table line that cannot be parsed, counting lines from 1.
+    InvalidTable {
+        line: usize,
+        reason: &'static str,
+    },
+    /// A named port without wires or with the name of another port on the same side.
+    InvalidPort {
+        complex: &'static str,
+        name: &'static str,
+        reason: &'static str,
+    },
+    /// A gate of a variadic kind built without any input.
+    NoInputs(&'static str),
+    /// A gate kind name that `GateKind::from_str` does not know.
+    UnknownGateKind(String),
+    /// A component in a complex handed to a model that only simulates gates.
+    UnsupportedComponent {
+        model: &'static str,
+        component: &'static str,
+    },
+    Oscillation(Oscillation),
+}
+
+impl Display for BinariiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinariiError::InputOutOfRange {
+                element,
+                index,
+                len,
+            } => write!(f, "{} has {} inputs, no input {}", element, len, index),
+            BinariiError::OutputOutOfRange {
+                element,
+                index,
+                len,
+            } => write!(f, "{} has {} outputs, no output {}", element, len, index),
+            BinariiError::WireOutOfRange { index, len } => {
+                write!(f, "bus has {} wires, no wire {}", len, index)
+            }
+            BinariiError::WidthMismatch { expected, found } => {
+                write!(f, "expected {} wires, found {}", expected, found)
+            }
+            BinariiError::UnconnectedWire { complex, wire } => {
+                write!(f, "{} is not connected to any element of {}", wire, complex)
+            }
+            BinariiError::InvalidSnapshot(reason) => write!(f, "invalid snapshot: {}", reason),
+            BinariiError::InvalidTable { line, reason } => {
+                write!(f, "invalid truth table at line {}: {}", line, reason)
+            }
+            BinariiError::InvalidPort {
+                complex,
+                name,
+                reason,
+            } => write!(f, "invalid port {} of {}: {}", name, complex, reason),
+            BinariiError::NoInputs(kind) => write!(f, "{} needs at least one input", kind),
+            BinariiError::UnknownGateKind(name) => write!(f, "unknown gate kind {}", name),
+            BinariiError::UnsupportedComponent { model, component } => {
+                write!(f, "{} cannot simulate component {}", model, component)
+            }
+            BinariiError::Oscillation(oscillation) => write!(f, "{}", oscillation),
+        }
+    }
+}
+
+impl Error for BinariiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BinariiError::Oscillation(oscillation) => Some(oscillation),
+            _ => None,
+        }
+    }
+}
+
+impl From<Oscillation> for BinariiError {
+    fn from(oscillation: Oscillation) -> Self {
+        BinariiError::Oscillation(oscillation)
+    }
+}
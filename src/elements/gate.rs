@@ -1,95 +1,262 @@
+use crate::elements::error::BinariiError;
+use crate::elements::signal::{Level, Signal};
 use crate::elements::wire::Wire;
 use crate::elements::Conduct;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub const DEFAULT_DELAY: u32 = 1;
+
+/// What a gate computes from its inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GateKind {
+    And,
+    Or,
+    Xor,
+    Not,
+    Nand,
+    Nor,
+    Xnor,
+    Buf,
+    /// Drives a fixed value and has no inputs.
+    Const(bool),
+    /// Drives the first input while the second is high and floats otherwise.
+    TriState,
+}
+
+impl GateKind {
+    pub const ALL: [GateKind; 11] = [
+        GateKind::And,
+        GateKind::Or,
+        GateKind::Xor,
+        GateKind::Not,
+        GateKind::Nand,
+        GateKind::Nor,
+        GateKind::Xnor,
+        GateKind::Buf,
+        GateKind::Const(false),
+        GateKind::Const(true),
+        GateKind::TriState,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GateKind::And => "and",
+            GateKind::Or => "or",
+            GateKind::Xor => "xor",
+            GateKind::Not => "not",
+            GateKind::Nand => "nand",
+            GateKind::Nor => "nor",
+            GateKind::Xnor => "xnor",
+            GateKind::Buf => "buf",
+            GateKind::Const(false) => "const0",
+            GateKind::Const(true) => "const1",
+            GateKind::TriState => "tri_state",
+        }
+    }
+
+    /// Returns the number of inputs, or `None` for kinds taking one or more.
+    pub fn arity(self) -> Option<usize> {
+        match self {
+            GateKind::Not | GateKind::Buf => Some(1),
+            GateKind::Const(_) => Some(0),
+            GateKind::TriState => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Computes the output for `inputs`, treating missing inputs as `X`.
+    pub fn eval(self, mut inputs: impl Iterator<Item = Signal>) -> Signal {
+        let first = inputs.next().unwrap_or(Signal::X);
+        match self {
+            GateKind::And => inputs.fold(first, and),
+            GateKind::Or => inputs.fold(first, or),
+            GateKind::Xor => inputs.fold(first, xor),
+            GateKind::Nand => not(inputs.fold(first, and)),
+            GateKind::Nor => not(inputs.fold(first, or)),
+            GateKind::Xnor => not(inputs.fold(first, xor)),
+            GateKind::Not => not(first),
+            GateKind::Buf => Signal::logic(first.high(), first.low()),
+            GateKind::Const(value) => Signal::splat(if value { Level::High } else { Level::Low }),
+            GateKind::TriState => {
+                let enable = inputs.next().unwrap_or(Signal::X);
+                let on = enable.high();
+                let off = enable.low();
+                let x = !(on | off) | on & first.unknown;
+                Signal {
+                    value: on & first.value | x,
+                    unknown: off | x,
+                }
+            }
+        }
+    }
+}
+
+fn and(in_1: Signal, in_2: Signal) -> Signal {
+    Signal::logic(in_1.high() & in_2.high(), in_1.low() | in_2.low())
+}
+
+fn or(in_1: Signal, in_2: Signal) -> Signal {
+    Signal::logic(in_1.high() | in_2.high(), in_1.low() & in_2.low())
+}
+
+fn xor(in_1: Signal, in_2: Signal) -> Signal {
+    let known = !(in_1.unknown | in_2.unknown);
+    let value = in_1.value ^ in_2.value;
+    Signal::logic(known & value, known & !value)
+}
+
+fn not(input: Signal) -> Signal {
+    Signal::logic(input.low(), input.high())
+}
+
+impl Display for GateKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for GateKind {
+    type Err = BinariiError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        GateKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| BinariiError::UnknownGateKind(name.to_string()))
+    }
+}
 
 #[derive(Clone)]
 pub struct Gate {
-    in_1: Wire,
-    in_2: Wire,
+    inputs: Vec<Wire>,
     out: Wire,
-    act: fn(bool, bool) -> bool,
-    tp: &'static str,
+    kind: GateKind,
+    delay: u32,
 }
 
 impl Gate {
-    pub fn and(in_1: Wire, in_2: Wire, out: Wire) -> Self {
+    /// Builds a gate of any kind, panicking when the number of inputs does not fit it.
+    pub fn new(kind: GateKind, inputs: Vec<Wire>, out: Wire) -> Self {
+        Self::try_new(kind, inputs, out).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(kind: GateKind, inputs: Vec<Wire>, out: Wire) -> Result<Self, BinariiError> {
+        match kind.arity() {
+            Some(arity) if inputs.len() != arity => {
+                return Err(BinariiError::WidthMismatch {
+                    expected: arity,
+                    found: inputs.len(),
+                });
+            }
+            None if inputs.is_empty() => return Err(BinariiError::NoInputs(kind.name())),
+            _ => {}
+        }
         let gate = Self {
-            in_1,
-            in_2,
+            inputs,
             out,
-            act: |in_1, in_2| in_1 && in_2,
-            tp: "and",
+            kind,
+            delay: DEFAULT_DELAY,
         };
         gate.conduct();
-        gate
+        Ok(gate)
+    }
+
+    pub fn and(in_1: Wire, in_2: Wire, out: Wire) -> Self {
+        Self::and_n(vec![in_1, in_2], out)
     }
 
     pub fn or(in_1: Wire, in_2: Wire, out: Wire) -> Self {
-        let gate = Self {
-            in_1,
-            in_2,
-            out,
-            act: |in_1, in_2| in_1 || in_2,
-            tp: "or",
-        };
-        gate.conduct();
-        gate
+        Self::or_n(vec![in_1, in_2], out)
     }
 
     pub fn xor(in_1: Wire, in_2: Wire, out: Wire) -> Self {
-        let gate = Self {
-            in_1,
-            in_2,
-            out,
-            act: |in_1, in_2| in_1 ^ in_2,
-            tp: "xor",
-        };
-        gate.conduct();
-        gate
+        Self::xor_n(vec![in_1, in_2], out)
     }
 
     pub fn not(in_1: Wire, out: Wire) -> Self {
-        let gate = Self {
-            in_1,
-            in_2: Wire::new(),
-            out,
-            act: |in_1, _| !in_1,
-            tp: "not",
-        };
-        gate.conduct();
-        gate
+        Self::new(GateKind::Not, vec![in_1], out)
     }
 
     pub fn nor(in_1: Wire, in_2: Wire, out: Wire) -> Self {
-        let gate = Self {
-            in_1,
-            in_2,
-            out,
-            act: |in_1, in_2| !(in_1 || in_2),
-            tp: "nor",
-        };
-        gate.conduct();
-        gate
+        Self::nor_n(vec![in_1, in_2], out)
     }
 
     pub fn nand(in_1: Wire, in_2: Wire, out: Wire) -> Self {
-        let gate = Self {
-            in_1,
-            in_2,
-            out,
-            act: |in_1, in_2| !(in_1 && in_2),
-            tp: "nand",
-        };
-        gate.conduct();
-        gate
+        Self::nand_n(vec![in_1, in_2], out)
+    }
+
+    pub fn xnor(in_1: Wire, in_2: Wire, out: Wire) -> Self {
+        Self::xnor_n(vec![in_1, in_2], out)
+    }
+
+    /// Copies `input` to `out`, turning `Z` into `X`.
+    pub fn buf(input: Wire, out: Wire) -> Self {
+        Self::new(GateKind::Buf, vec![input], out)
+    }
+
+    /// Drives `out` with a fixed value.
+    pub fn constant(value: bool, out: Wire) -> Self {
+        Self::new(GateKind::Const(value), vec![], out)
+    }
+
+    /// And of any number of inputs, evaluated in one step.
+    pub fn and_n(inputs: Vec<Wire>, out: Wire) -> Self {
+        Self::new(GateKind::And, inputs, out)
+    }
+
+    pub fn or_n(inputs: Vec<Wire>, out: Wire) -> Self {
+        Self::new(GateKind::Or, inputs, out)
+    }
+
+    /// Parity of the inputs: high when an odd number of them is high.
+    pub fn xor_n(inputs: Vec<Wire>, out: Wire) -> Self {
+        Self::new(GateKind::Xor, inputs, out)
+    }
+
+    pub fn nand_n(inputs: Vec<Wire>, out: Wire) -> Self {
+        Self::new(GateKind::Nand, inputs, out)
+    }
+
+    pub fn nor_n(inputs: Vec<Wire>, out: Wire) -> Self {
+        Self::new(GateKind::Nor, inputs, out)
+    }
+
+    /// High when an even number of the inputs is high.
+    pub fn xnor_n(inputs: Vec<Wire>, out: Wire) -> Self {
+        Self::new(GateKind::Xnor, inputs, out)
+    }
+
+    /// Drives `out` with `input` while `enable` is high and leaves it floating otherwise.
+    pub fn tri_state(input: Wire, enable: Wire, out: Wire) -> Self {
+        Self::new(GateKind::TriState, vec![input, enable], out)
+    }
+
+    /// Sets the propagation delay used by the timing simulation, at least one time unit.
+    pub fn with_delay(mut self, delay: u32) -> Self {
+        self.set_delay(delay);
+        self
+    }
+
+    pub fn set_delay(&mut self, delay: u32) {
+        self.delay = delay.max(1);
+    }
+
+    pub fn delay(&self) -> u32 {
+        self.delay
     }
 
     pub fn wire_in_1(&mut self, wire: Wire) {
-        self.in_1 = wire;
+        self.inputs[0] = wire;
     }
 
+    /// Panics for a gate with fewer than two inputs; `try_wire_in` reports it instead.
     pub fn wire_in_2(&mut self, wire: Wire) {
-        self.in_2 = wire;
+        self.inputs[1] = wire;
+    }
+
+    pub fn wire_in(&mut self, index: usize, wire: Wire) {
+        self.inputs[index] = wire;
     }
 
     pub fn wire_out(&mut self, wire: Wire) {
@@ -97,28 +264,74 @@ impl Gate {
     }
 
     pub fn get_in_1(&self) -> Wire {
-        self.in_1.clone()
+        self.inputs[0].clone()
     }
 
+    /// Panics for a gate with fewer than two inputs; `try_get_in` reports it instead.
     pub fn get_in_2(&self) -> Wire {
-        self.in_2.clone()
+        self.inputs[1].clone()
+    }
+
+    pub fn get_in(&self, index: usize) -> Wire {
+        self.inputs[index].clone()
+    }
+
+    pub fn inputs(&self) -> &[Wire] {
+        &self.inputs
     }
 
     pub fn get_out(&self) -> Wire {
         self.out.clone()
     }
+
+    /// Returns input `index`, counting from 0 for `in_1`.
+    pub fn try_get_in(&self, index: usize) -> Result<Wire, BinariiError> {
+        match self.inputs.get(index) {
+            Some(wire) => Ok(wire.clone()),
+            None => Err(self.input_out_of_range(index)),
+        }
+    }
+
+    pub fn try_wire_in(&mut self, index: usize, wire: Wire) -> Result<(), BinariiError> {
+        if index >= self.inputs.len() {
+            return Err(self.input_out_of_range(index));
+        }
+        self.wire_in(index, wire);
+        Ok(())
+    }
+
+    fn input_out_of_range(&self, index: usize) -> BinariiError {
+        BinariiError::InputOutOfRange {
+            element: self.tp(),
+            index,
+            len: self.inputs.len(),
+        }
+    }
+
+    pub fn tp(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    pub fn kind(&self) -> GateKind {
+        self.kind
+    }
 }
 
+/// Names the wires by label, or by their position as `in{k}` and `out`.
 impl Display for Gate {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        writeln!(
-            f,
-            "IN:{:#x}\nIN:{:#x}\nOUT:{:#x}",
-            self.in_1.id(),
-            self.in_2.id(),
-            self.out.id()
-        )?;
-        writeln!(f, "{}({}, {})->{}", self.tp, self.in_1, self.in_2, self.out)
+        for (i, wire) in self.inputs.iter().enumerate() {
+            writeln!(f, "IN:{}", wire.label().unwrap_or(format!("in{}", i)))?;
+        }
+        writeln!(f, "OUT:{}", self.out.label().unwrap_or("out".to_string()))?;
+        write!(f, "{}(", self.kind)?;
+        for (i, wire) in self.inputs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", wire)?;
+        }
+        writeln!(f, ")->{}", self.out)
     }
 }
 
@@ -131,8 +344,7 @@ impl Default for Gate {
 impl Conduct for Gate {
     #[inline]
     fn conduct(&self) {
-        let in_1 = self.in_1.get();
-        let in_2 = self.in_2.get();
-        self.out.set((self.act)(in_1, in_2));
+        let value = self.kind.eval(self.inputs.iter().map(|wire| wire.signal()));
+        self.out.set_signal(value);
     }
 }
@@ -0,0 +1,237 @@
+use crate::elements::component::Component;
+use crate::elements::error::BinariiError;
+use crate::elements::signal::{Signal, LANES};
+use crate::elements::wire::Wire;
+use crate::elements::Conduct;
+
+/// Largest number of inputs, keeping tables at a few megabytes.
+pub const MAX_LUT_INPUTS: usize = 20;
+
+/// Lookup table with `k` inputs and up to 64 outputs, evaluated from a truth table.
+///
+/// Row `r` of the table holds the outputs for the inputs where input `i` is bit `i` of `r`;
+/// output `o` is bit `o` of the row. Undefined rows and unknown inputs give `X`.
+#[derive(Clone, Debug)]
+pub struct Lut {
+    inputs: Vec<Wire>,
+    outputs: Vec<Wire>,
+    values: Vec<u64>,
+    defined: Vec<u64>,
+}
+
+impl Lut {
+    pub fn new(inputs: Vec<Wire>, outputs: Vec<Wire>, table: &[u64]) -> Result<Self, BinariiError> {
+        check_size(&inputs, &outputs)?;
+        if table.len() != 1 << inputs.len() {
+            return Err(BinariiError::WidthMismatch {
+                expected: 1 << inputs.len(),
+                found: table.len(),
+            });
+        }
+        let mask = mask(outputs.len());
+        Ok(Self::with_rows(
+            inputs,
+            outputs,
+            table.iter().map(|row| row & mask).collect(),
+            vec![mask; table.len()],
+        ))
+    }
+
+    /// Builds the table by calling `f` with every row index.
+    pub fn from_fn(
+        inputs: Vec<Wire>,
+        outputs: Vec<Wire>,
+        f: impl Fn(u64) -> u64,
+    ) -> Result<Self, BinariiError> {
+        check_size(&inputs, &outputs)?;
+        let table = (0..1u64 << inputs.len()).map(f).collect::<Vec<_>>();
+        Self::new(inputs, outputs, &table)
+    }
+
+    /// Parses a truth table with one `inputs outputs` row per line, e.g. `01 1` for the row
+    /// where input 0 is low and input 1 is high. Digits follow the wire order, `-` in the
+    /// outputs leaves a bit undefined, and `#` starts a comment.
+    pub fn parse(inputs: Vec<Wire>, outputs: Vec<Wire>, text: &str) -> Result<Self, BinariiError> {
+        check_size(&inputs, &outputs)?;
+        let mut values = vec![0; 1 << inputs.len()];
+        let mut defined = vec![0; 1 << inputs.len()];
+        for (line, text) in text.lines().enumerate() {
+            let invalid = |reason| BinariiError::InvalidTable {
+                line: line + 1,
+                reason,
+            };
+            let text = text.split('#').next().unwrap().trim();
+            if text.is_empty() {
+                continue;
+            }
+            let (row, out) = text
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| invalid("expected inputs and outputs"))?;
+            let out = out.trim();
+            if row.len() != inputs.len() || out.len() != outputs.len() {
+                return Err(invalid("wrong number of digits"));
+            }
+
+            let mut index = 0;
+            for (i, digit) in row.chars().enumerate() {
+                match digit {
+                    '0' => {}
+                    '1' => index |= 1 << i,
+                    _ => return Err(invalid("inputs must be 0 or 1")),
+                }
+            }
+            if defined[index] != 0 {
+                return Err(invalid("duplicate row"));
+            }
+            for (o, digit) in out.chars().enumerate() {
+                match digit {
+                    '0' => defined[index] |= 1 << o,
+                    '1' => {
+                        defined[index] |= 1 << o;
+                        values[index] |= 1 << o;
+                    }
+                    '-' => {}
+                    _ => return Err(invalid("outputs must be 0, 1 or -")),
+                }
+            }
+        }
+        Ok(Self::with_rows(inputs, outputs, values, defined))
+    }
+
+    fn with_rows(
+        inputs: Vec<Wire>,
+        outputs: Vec<Wire>,
+        values: Vec<u64>,
+        defined: Vec<u64>,
+    ) -> Self {
+        let lut = Self {
+            inputs,
+            outputs,
+            values,
+            defined,
+        };
+        lut.conduct();
+        lut
+    }
+
+    /// Returns the output bits of `row` and the mask of the defined ones.
+    pub fn row(&self, row: usize) -> (u64, u64) {
+        (self.values[row], self.defined[row])
+    }
+}
+
+fn check_size(inputs: &[Wire], outputs: &[Wire]) -> Result<(), BinariiError> {
+    if inputs.len() > MAX_LUT_INPUTS {
+        return Err(BinariiError::InputOutOfRange {
+            element: "lut",
+            index: inputs.len() - 1,
+            len: MAX_LUT_INPUTS,
+        });
+    }
+    if outputs.len() > 64 {
+        return Err(BinariiError::OutputOutOfRange {
+            element: "lut",
+            index: outputs.len() - 1,
+            len: 64,
+        });
+    }
+    Ok(())
+}
+
+fn mask(bits: usize) -> u64 {
+    if bits == 64 {
+        u64::MAX
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+impl Conduct for Lut {
+    fn conduct(&self) {
+        let inputs = self.inputs.iter().map(|w| w.signal()).collect::<Vec<_>>();
+        let mut outputs = vec![Signal::default(); self.outputs.len()];
+        for lane in 0..LANES {
+            let bit = 1 << lane;
+            let mut row = 0;
+            let mut known = true;
+            for (i, input) in inputs.iter().enumerate() {
+                if input.unknown & bit != 0 {
+                    known = false;
+                    break;
+                }
+                if input.value & bit != 0 {
+                    row |= 1 << i;
+                }
+            }
+
+            let (values, defined) = if known { self.row(row) } else { (0, 0) };
+            for (o, output) in outputs.iter_mut().enumerate() {
+                if defined >> o & 1 == 0 {
+                    output.value |= bit;
+                    output.unknown |= bit;
+                } else if values >> o & 1 == 1 {
+                    output.value |= bit;
+                }
+            }
+        }
+
+        for (wire, signal) in self.outputs.iter().zip(outputs) {
+            wire.set_signal(signal);
+        }
+    }
+}
+
+impl Component for Lut {
+    fn tp(&self) -> &'static str {
+        "lut"
+    }
+
+    fn input(&self) -> Vec<Wire> {
+        self.inputs.clone()
+    }
+
+    fn output(&self) -> Vec<Wire> {
+        self.outputs.clone()
+    }
+
+    fn wire_input(&mut self, port: usize, wire: Wire) -> Result<(), BinariiError> {
+        let len = self.inputs.len();
+        let input = self
+            .inputs
+            .get_mut(port)
+            .ok_or(BinariiError::InputOutOfRange {
+                element: "lut",
+                index: port,
+                len,
+            })?;
+        *input = wire;
+        Ok(())
+    }
+
+    fn wire_output(&mut self, port: usize, wire: Wire) -> Result<(), BinariiError> {
+        let len = self.outputs.len();
+        let output = self
+            .outputs
+            .get_mut(port)
+            .ok_or(BinariiError::OutputOutOfRange {
+                element: "lut",
+                index: port,
+                len,
+            })?;
+        *output = wire;
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Component> {
+        Box::new(self.clone())
+    }
+
+    /// A table has no state besides its wires.
+    fn box_instantiate(&self) -> Box<dyn Component> {
+        Box::new(self.clone())
+    }
+
+    fn is_combinational(&self) -> bool {
+        true
+    }
+}
@@ -1,7 +1,19 @@
+pub mod analysis;
+pub mod bits;
+pub mod builder;
 pub mod bus;
 pub mod complex;
+pub mod component;
+pub mod error;
 pub mod gate;
+pub mod lut;
+pub mod netlist;
+pub mod observer;
 pub mod oscillator;
+pub mod program;
+pub mod signal;
+pub mod snapshot;
+pub mod timing;
 pub mod wire;
 
 pub trait Conduct {
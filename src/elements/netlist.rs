@@ -0,0 +1,85 @@
+use crate::elements::complex::{Complex, Element, ElementId};
+use bevy::utils::HashMap;
+
+/// A `Complex` hierarchy flattened into a single level of gates.
+///
+/// Components are opaque and are carried over as they are.
+///
+/// The gates keep their wires, so the netlist and the source complex drive the same nets.
+/// Use `Complex::instantiate` on the result for an independent copy.
+pub struct Netlist {
+    complex: Complex,
+    paths: HashMap<ElementId, String>,
+}
+
+impl Netlist {
+    pub fn new(complex: &Complex) -> Self {
+        let mut builder = Complex::builder(complex.tp());
+        for wire in complex.get_in_bus(0, complex.input_len()).wires() {
+            builder.add_input(wire.clone());
+        }
+        for wire in complex.get_out_bus(0, complex.output_len()).wires() {
+            builder.add_output(wire.clone());
+        }
+
+        let mut paths = HashMap::new();
+        let mut path = vec![complex.tp().to_string()];
+        collect(complex, &mut path, &mut |path, element| {
+            let leaf = match element {
+                Element::Gate(gate) => Element::Gate(gate.clone()),
+                Element::Component(component) => Element::Component(component.clone()),
+                Element::Complex(_) => unreachable!("collect descends into complexes"),
+            };
+            let id = builder.add_element(leaf);
+            paths.insert(id, path);
+        });
+
+        let mut flat = builder.finish();
+        flat.set_mode(complex.mode());
+        flat.set_settle_limit(complex.settle_limit());
+        flat.name_ports_like(complex);
+        Self {
+            complex: flat,
+            paths,
+        }
+    }
+
+    pub fn complex(&self) -> &Complex {
+        &self.complex
+    }
+
+    pub fn into_complex(self) -> Complex {
+        self.complex
+    }
+
+    /// Returns the hierarchical name of a gate, e.g. `byte_sum/sum#0/half_sum#1/xor#0`.
+    pub fn path(&self, id: ElementId) -> Option<&str> {
+        self.paths.get(&id).map(|path| path.as_str())
+    }
+
+    pub fn gate_count(&self) -> usize {
+        self.complex
+            .elements()
+            .filter(|(_, element)| matches!(element, Element::Gate(_)))
+            .count()
+    }
+
+    /// Counts the elements of type `tp`.
+    pub fn count(&self, tp: &str) -> usize {
+        self.complex
+            .elements()
+            .filter(|(_, element)| element.tp() == tp)
+            .count()
+    }
+}
+
+fn collect(complex: &Complex, path: &mut Vec<String>, f: &mut impl FnMut(String, &Element)) {
+    for (id, element) in complex.elements() {
+        path.push(format!("{}#{}", element.tp(), id));
+        match element {
+            Element::Complex(complex) => collect(complex, path, f),
+            _ => f(path.join("/"), element),
+        }
+        path.pop();
+    }
+}
@@ -0,0 +1,79 @@
+use crate::elements::signal::Signal;
+use crate::elements::wire::Wire;
+
+/// A wire value change reported to an observer at the end of a top-level tick.
+#[derive(Debug, Clone)]
+pub struct WireChange {
+    /// Number of the top-level `conduct` or `settle` call that produced the change, counting
+    /// from 1.
+    pub tick: u64,
+    pub wire: Wire,
+    pub old: Signal,
+    pub new: Signal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(usize);
+
+type Callback = Box<dyn FnMut(&WireChange) + Send>;
+
+struct Observer {
+    id: ObserverId,
+    wires: Vec<(Wire, Signal)>,
+    callback: Callback,
+}
+
+/// Observers registered on a `Complex`, with the values they last saw.
+#[derive(Default)]
+pub(crate) struct Observers {
+    tick: u64,
+    next_id: usize,
+    observers: Vec<Observer>,
+}
+
+impl Observers {
+    pub(crate) fn add(
+        &mut self,
+        wires: &[Wire],
+        callback: impl FnMut(&WireChange) + Send + 'static,
+    ) -> ObserverId {
+        let id = ObserverId(self.next_id);
+        self.next_id += 1;
+        self.observers.push(Observer {
+            id,
+            wires: wires.iter().map(|w| (w.clone(), w.signal())).collect(),
+            callback: Box::new(callback),
+        });
+        id
+    }
+
+    pub(crate) fn remove(&mut self, id: ObserverId) -> bool {
+        let len = self.observers.len();
+        self.observers.retain(|observer| observer.id != id);
+        self.observers.len() != len
+    }
+
+    pub(crate) fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Records `tick` and reports every observed wire whose value changed since the last one.
+    pub(crate) fn notify(&mut self, tick: u64) {
+        self.tick = tick;
+        for observer in self.observers.iter_mut() {
+            for (wire, last) in observer.wires.iter_mut() {
+                let new = wire.signal();
+                if new != *last {
+                    let change = WireChange {
+                        tick: self.tick,
+                        wire: wire.clone(),
+                        old: *last,
+                        new,
+                    };
+                    *last = new;
+                    (observer.callback)(&change);
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,15 @@
-use crate::elements::wire::Wire;
+use crate::elements::component::Component;
+use crate::elements::error::BinariiError;
+use crate::elements::wire::{Wire, WireMap};
 use crate::elements::Conduct;
-use std::cell::Cell;
-use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub struct Oscillator {
     out: Wire,
     half_period: u32,
-    counter: Rc<Cell<u32>>,
+    counter: Arc<AtomicU32>,
 }
 
 impl Oscillator {
@@ -20,7 +22,20 @@ impl Oscillator {
         Self {
             out,
             half_period: half_period as u32,
-            counter: Rc::new(Cell::new(0)),
+            counter: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    /// Returns an independent copy driving a fresh wire with its own counter.
+    pub fn instantiate(&self) -> Self {
+        self.instantiate_with(&mut WireMap::default())
+    }
+
+    pub(crate) fn instantiate_with(&self, wires: &mut WireMap) -> Self {
+        Self {
+            out: wires.get(&self.out),
+            half_period: self.half_period,
+            counter: Arc::new(AtomicU32::new(self.counter.load(Ordering::Relaxed))),
         }
     }
 
@@ -35,11 +50,68 @@ impl Oscillator {
 
 impl Conduct for Oscillator {
     fn conduct(&self) {
-        let count = self.counter.get();
-        self.counter.set(count + 1);
+        let count = self.counter.fetch_add(1, Ordering::Relaxed);
         if count == self.half_period - 1 {
-            self.counter.set(0);
+            self.counter.store(0, Ordering::Relaxed);
             self.out.set(!self.out.get());
         }
     }
 }
+
+impl Component for Oscillator {
+    fn tp(&self) -> &'static str {
+        "oscillator"
+    }
+
+    fn input(&self) -> Vec<Wire> {
+        Vec::new()
+    }
+
+    fn output(&self) -> Vec<Wire> {
+        vec![self.out.clone()]
+    }
+
+    fn wire_input(&mut self, port: usize, _: Wire) -> Result<(), BinariiError> {
+        Err(BinariiError::InputOutOfRange {
+            element: "oscillator",
+            index: port,
+            len: 0,
+        })
+    }
+
+    fn wire_output(&mut self, port: usize, wire: Wire) -> Result<(), BinariiError> {
+        if port != 0 {
+            return Err(BinariiError::OutputOutOfRange {
+                element: "oscillator",
+                index: port,
+                len: 1,
+            });
+        }
+        self.out = wire;
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Component> {
+        Box::new(self.clone())
+    }
+
+    fn save(&self) -> Vec<u8> {
+        self.counter.load(Ordering::Relaxed).to_le_bytes().to_vec()
+    }
+
+    fn load(&self, state: &[u8]) -> Result<(), BinariiError> {
+        let bytes = state
+            .try_into()
+            .map_err(|_| BinariiError::InvalidSnapshot("oscillator state is not 4 bytes"))?;
+        self.counter
+            .store(u32::from_le_bytes(bytes), Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn box_instantiate(&self) -> Box<dyn Component> {
+        Box::new(Self {
+            counter: Arc::new(AtomicU32::new(self.counter.load(Ordering::Relaxed))),
+            ..self.clone()
+        })
+    }
+}
@@ -0,0 +1,193 @@
+use crate::elements::bus::Bus;
+use crate::elements::complex::{Complex, Oscillation};
+use crate::elements::error::BinariiError;
+use crate::elements::gate::GateKind;
+use crate::elements::signal::{Signal, WireId};
+use crate::elements::wire::Wire;
+use crate::elements::Conduct;
+use bevy::utils::HashMap;
+use std::cell::RefCell;
+
+/// A `Complex` flattened into a linear list of gate instructions over local registers.
+///
+/// Only the input and output wires of the source complex are read and written; internal
+/// wires of the hierarchy are not updated while the program runs.
+/// Components have no instruction form, so a complex holding one is rejected.
+#[derive(Clone)]
+pub struct Program {
+    tp: &'static str,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    instructions: Vec<Instruction>,
+    /// Per instruction, the driver slot it writes when its destination has several drivers.
+    slots: Vec<Option<(usize, usize)>>,
+    loads: Vec<(usize, Wire)>,
+    stores: Vec<(usize, Wire)>,
+    /// The wire each register was taken from.
+    wires: Vec<Wire>,
+    /// Names of `wires` in the dumps of the source complex.
+    names: Vec<String>,
+    registers: RefCell<Vec<Signal>>,
+    drives: RefCell<Vec<Vec<Signal>>>,
+    limit: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub kind: GateKind,
+    pub src: Vec<usize>,
+    pub dst: usize,
+}
+
+impl Program {
+    pub fn new(complex: &Complex) -> Result<Self, BinariiError> {
+        complex.check_gates_only("program")?;
+        let mut wires = Vec::new();
+        let mut ids = HashMap::<WireId, usize>::new();
+        let mut register = |wire: &Wire| {
+            *ids.entry(wire.id()).or_insert_with(|| {
+                wires.push(wire.clone());
+                wires.len() - 1
+            })
+        };
+
+        let mut instructions = Vec::new();
+        complex.for_each_gate(&mut |gate| {
+            instructions.push(Instruction {
+                kind: gate.kind(),
+                src: gate.inputs().iter().map(&mut register).collect(),
+                dst: register(&gate.get_out()),
+            });
+        });
+
+        let input = complex.get_in_bus(0, complex.input_len()).wires().to_vec();
+        let output = complex
+            .get_out_bus(0, complex.output_len())
+            .wires()
+            .to_vec();
+        let loads = input.iter().map(|w| (register(w), w.clone())).collect();
+        let stores = output.iter().map(|w| (register(w), w.clone())).collect();
+
+        let mut drivers = vec![0; wires.len()];
+        for instruction in instructions.iter() {
+            drivers[instruction.dst] += 1;
+        }
+        let mut shared = HashMap::<usize, usize>::new();
+        let mut drives = Vec::<Vec<Signal>>::new();
+        let slots = instructions
+            .iter()
+            .map(|instruction| {
+                if drivers[instruction.dst] < 2 {
+                    return None;
+                }
+                let idx = *shared.entry(instruction.dst).or_insert_with(|| {
+                    drives.push(Vec::new());
+                    drives.len() - 1
+                });
+                drives[idx].push(Signal::Z);
+                Some((idx, drives[idx].len() - 1))
+            })
+            .collect();
+
+        let names = complex.net_names();
+        Ok(Self {
+            tp: complex.tp(),
+            names: wires.iter().map(|wire| names[&wire.id()].clone()).collect(),
+            input,
+            output,
+            instructions,
+            slots,
+            loads,
+            stores,
+            registers: RefCell::new(wires.iter().map(|w| w.signal()).collect()),
+            wires,
+            drives: RefCell::new(drives),
+            limit: complex.settle_limit(),
+        })
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    pub fn get_in(&self, wire_id: usize) -> Wire {
+        self.input[wire_id].clone()
+    }
+
+    pub fn get_out(&self, wire_id: usize) -> Wire {
+        self.output[wire_id].clone()
+    }
+
+    pub fn get_in_bus(&self, offset: usize, len: usize) -> Bus {
+        Bus::with_wires(self.input[offset..offset + len].to_vec())
+    }
+
+    pub fn get_out_bus(&self, offset: usize, len: usize) -> Bus {
+        Bus::with_wires(self.output[offset..offset + len].to_vec())
+    }
+
+    /// Runs the instructions until no register changes, returning the number of passes.
+    ///
+    /// Destinations written by several instructions are resolved like the shared nets of a
+    /// `Complex`. The outputs are stored even when the program does not settle.
+    pub fn settle(&self) -> Result<usize, Oscillation> {
+        let mut registers = self.registers.borrow_mut();
+        let mut drives = self.drives.borrow_mut();
+        for (reg, wire) in self.loads.iter() {
+            registers[*reg] = wire.signal();
+        }
+
+        let mut result = Err(Vec::new());
+        for iteration in 1..=self.limit {
+            let mut toggling = Vec::new();
+            for (instruction, slot) in self.instructions.iter().zip(self.slots.iter()) {
+                let mut value = instruction
+                    .kind
+                    .eval(instruction.src.iter().map(|&reg| registers[reg]));
+                if let Some((shared, slot)) = *slot {
+                    drives[shared][slot] = value;
+                    value = Signal::resolve(&drives[shared]).0;
+                }
+                if let Some(pull) = self.wires[instruction.dst].pull() {
+                    value = value.pulled(pull);
+                }
+                if registers[instruction.dst] != value {
+                    registers[instruction.dst] = value;
+                    toggling.push(instruction.dst);
+                }
+            }
+            if toggling.is_empty() {
+                result = Ok(iteration);
+                break;
+            }
+            result = Err(toggling);
+        }
+
+        for (reg, wire) in self.stores.iter() {
+            wire.set_signal(registers[*reg]);
+        }
+        result.map_err(|mut toggling| {
+            toggling.sort_unstable();
+            toggling.dedup();
+            Oscillation {
+                complex: self.tp,
+                iterations: self.limit,
+                wires: toggling
+                    .iter()
+                    .map(|&reg| self.wires[reg].clone())
+                    .collect(),
+                names: toggling
+                    .iter()
+                    .map(|&reg| self.names[reg].clone())
+                    .collect(),
+            }
+        })
+    }
+}
+
+impl Conduct for Program {
+    /// Like `settle`, leaving the outputs at their last values when the program oscillates.
+    fn conduct(&self) {
+        let _ = self.settle();
+    }
+}
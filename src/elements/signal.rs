@@ -0,0 +1,354 @@
+use bevy::utils::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+
+const CHUNK_SIZE: usize = 1024;
+
+/// Number of independent simulation lanes carried by every wire.
+pub const LANES: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WireId(usize);
+
+impl WireId {
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl Display for WireId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "w{}", self.0)
+    }
+}
+
+/// Logic level of a single lane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Level {
+    Low,
+    High,
+    /// Not driven.
+    Z,
+    /// Driven to conflicting values.
+    X,
+}
+
+/// Weak level a wire settles to in the lanes that nothing drives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pull {
+    Up,
+    Down,
+}
+
+/// Levels of all lanes of a wire, stored as a value plane and an unknown plane.
+///
+/// A lane is `Low` or `High` when its unknown bit is clear, `Z` when only the unknown bit is
+/// set and `X` when both bits are set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Signal {
+    pub value: u64,
+    pub unknown: u64,
+}
+
+impl Signal {
+    pub const LOW: Signal = Signal::known(0);
+    pub const HIGH: Signal = Signal::known(u64::MAX);
+    pub const Z: Signal = Signal {
+        value: 0,
+        unknown: u64::MAX,
+    };
+    pub const X: Signal = Signal {
+        value: u64::MAX,
+        unknown: u64::MAX,
+    };
+
+    pub const fn known(value: u64) -> Self {
+        Self { value, unknown: 0 }
+    }
+
+    /// Builds a signal from the lanes known to be high and known to be low, all other lanes
+    /// become `X`.
+    pub const fn logic(high: u64, low: u64) -> Self {
+        let x = !(high | low);
+        Self {
+            value: high | x,
+            unknown: x,
+        }
+    }
+
+    /// Lanes that are known to be high.
+    #[inline]
+    pub fn high(&self) -> u64 {
+        self.value & !self.unknown
+    }
+
+    /// Lanes that are known to be low.
+    #[inline]
+    pub fn low(&self) -> u64 {
+        !(self.value | self.unknown)
+    }
+
+    pub fn splat(level: Level) -> Self {
+        match level {
+            Level::Low => Self::LOW,
+            Level::High => Self::HIGH,
+            Level::Z => Self::Z,
+            Level::X => Self::X,
+        }
+    }
+
+    pub fn level(&self, lane: usize) -> Level {
+        match ((self.value >> lane) & 1, (self.unknown >> lane) & 1) {
+            (0, 0) => Level::Low,
+            (_, 0) => Level::High,
+            (0, _) => Level::Z,
+            _ => Level::X,
+        }
+    }
+
+    pub fn set_level(&mut self, lane: usize, level: Level) {
+        let bit = 1 << lane;
+        let other = Self::splat(level);
+        self.value = self.value & !bit | other.value & bit;
+        self.unknown = self.unknown & !bit | other.unknown & bit;
+    }
+
+    /// Lanes that are `Z`.
+    pub fn z(&self) -> u64 {
+        self.unknown & !self.value
+    }
+
+    /// Lanes that are `X`.
+    pub fn x(&self) -> u64 {
+        self.unknown & self.value
+    }
+
+    /// Replaces the `Z` lanes with the level of `pull`.
+    pub fn pulled(self, pull: Pull) -> Signal {
+        let z = self.z();
+        match pull {
+            Pull::Up => Signal {
+                value: self.value | z,
+                unknown: self.unknown & !z,
+            },
+            Pull::Down => Signal {
+                value: self.value,
+                unknown: self.unknown & !z,
+            },
+        }
+    }
+
+    /// Combines the signals of several drivers of one wire.
+    ///
+    /// `Z` lanes give way to the other drivers, lanes driven both low and high become `X`.
+    /// Returns the resolved signal and the mask of lanes where drivers fight.
+    pub fn resolve(drivers: &[Signal]) -> (Signal, u64) {
+        let mut low = 0;
+        let mut high = 0;
+        let mut x = 0;
+        for driver in drivers {
+            low |= driver.low();
+            high |= driver.high();
+            x |= driver.x();
+        }
+
+        let fighting = low & high;
+        let x = x | fighting;
+        let z = !(low | high | x);
+        let signal = Signal {
+            value: high | x,
+            unknown: x | z,
+        };
+        (signal, fighting)
+    }
+}
+
+pub(crate) struct Chunk {
+    values: [AtomicU64; CHUNK_SIZE],
+    unknown: [AtomicU64; CHUNK_SIZE],
+    pulls: [AtomicU8; CHUNK_SIZE],
+}
+
+impl Chunk {
+    fn new() -> Self {
+        Self {
+            values: std::array::from_fn(|_| AtomicU64::new(0)),
+            unknown: std::array::from_fn(|_| AtomicU64::new(0)),
+            pulls: std::array::from_fn(|_| AtomicU8::new(0)),
+        }
+    }
+
+    /// Returns the signal with the pull of the wire applied.
+    #[inline]
+    pub(crate) fn get(&self, id: WireId) -> Signal {
+        let signal = self.raw(id);
+        if signal.z() == 0 {
+            return signal;
+        }
+        match self.pull(id) {
+            Some(pull) => signal.pulled(pull),
+            None => signal,
+        }
+    }
+
+    /// Returns the signal as driven, with undriven lanes left at `Z`.
+    #[inline]
+    pub(crate) fn raw(&self, id: WireId) -> Signal {
+        let slot = id.0 % CHUNK_SIZE;
+        Signal {
+            value: self.values[slot].load(Ordering::Relaxed),
+            unknown: self.unknown[slot].load(Ordering::Relaxed),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set(&self, id: WireId, signal: Signal) {
+        let slot = id.0 % CHUNK_SIZE;
+        self.values[slot].store(signal.value, Ordering::Relaxed);
+        self.unknown[slot].store(signal.unknown, Ordering::Relaxed);
+    }
+
+    /// Returns the lanes known to be high, so two-valued reads see `X` and `Z` as low.
+    #[inline]
+    pub(crate) fn value(&self, id: WireId) -> u64 {
+        self.get(id).high()
+    }
+
+    #[inline]
+    pub(crate) fn pull(&self, id: WireId) -> Option<Pull> {
+        match self.pulls[id.0 % CHUNK_SIZE].load(Ordering::Relaxed) {
+            1 => Some(Pull::Down),
+            2 => Some(Pull::Up),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set_pull(&self, id: WireId, pull: Option<Pull>) {
+        let pull = match pull {
+            None => 0,
+            Some(Pull::Down) => 1,
+            Some(Pull::Up) => 2,
+        };
+        self.pulls[id.0 % CHUNK_SIZE].store(pull, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_lane(&self, id: WireId, lane: usize, value: bool) {
+        let slot = id.0 % CHUNK_SIZE;
+        let bit = 1 << lane;
+        if value {
+            self.values[slot].fetch_or(bit, Ordering::Relaxed);
+        } else {
+            self.values[slot].fetch_and(!bit, Ordering::Relaxed);
+        }
+        self.unknown[slot].fetch_and(!bit, Ordering::Relaxed);
+    }
+}
+
+/// Slot of one wire in the store, released for reuse when the last handle drops.
+pub(crate) struct Handle {
+    pub(crate) id: WireId,
+    pub(crate) chunk: Arc<Chunk>,
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        Signals::global().release(self);
+    }
+}
+
+#[derive(Default)]
+struct Slots {
+    chunks: Vec<Arc<Chunk>>,
+    handles: Vec<Weak<Handle>>,
+    free: Vec<WireId>,
+}
+
+/// Flat store holding the value of every wire, indexed by `WireId`.
+///
+/// Values are kept in fixed-size chunks so that handles stay valid while the store grows.
+/// The slot of a wire, with its pull and label, is freed when its last `Wire` handle drops
+/// and its id is handed to the next new wire.
+pub struct Signals {
+    slots: Mutex<Slots>,
+    labels: Mutex<HashMap<WireId, Arc<str>>>,
+}
+
+impl Signals {
+    pub fn global() -> &'static Signals {
+        static SIGNALS: OnceLock<Signals> = OnceLock::new();
+        SIGNALS.get_or_init(|| Signals {
+            slots: Mutex::new(Slots::default()),
+            labels: Mutex::new(HashMap::default()),
+        })
+    }
+
+    pub(crate) fn alloc(&self) -> Arc<Handle> {
+        let mut slots = self.slots.lock().unwrap();
+        let id = match slots.free.pop() {
+            Some(id) => id,
+            None => {
+                let id = WireId(slots.handles.len());
+                if id.0 / CHUNK_SIZE == slots.chunks.len() {
+                    slots.chunks.push(Arc::new(Chunk::new()));
+                }
+                slots.handles.push(Weak::new());
+                id
+            }
+        };
+        let handle = Arc::new(Handle {
+            id,
+            chunk: slots.chunks[id.0 / CHUNK_SIZE].clone(),
+        });
+        slots.handles[id.0] = Arc::downgrade(&handle);
+        handle
+    }
+
+    /// Returns the slot of a live wire.
+    pub(crate) fn handle(&self, id: WireId) -> Option<Arc<Handle>> {
+        let slots = self.slots.lock().unwrap();
+        slots.handles.get(id.0).and_then(Weak::upgrade)
+    }
+
+    fn release(&self, handle: &Handle) {
+        handle.chunk.set(handle.id, Signal::LOW);
+        handle.chunk.set_pull(handle.id, None);
+        self.labels.lock().unwrap().remove(&handle.id);
+        self.slots.lock().unwrap().free.push(handle.id);
+    }
+
+    /// Returns the number of live wires.
+    pub fn len(&self) -> usize {
+        let slots = self.slots.lock().unwrap();
+        slots.handles.len() - slots.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, id: WireId) -> Option<Signal> {
+        self.handle(id).map(|handle| handle.chunk.get(id))
+    }
+
+    pub fn set(&self, id: WireId, value: Signal) -> bool {
+        match self.handle(id) {
+            Some(handle) => {
+                handle.chunk.set(id, value);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn label(&self, id: WireId) -> Option<Arc<str>> {
+        self.labels.lock().unwrap().get(&id).cloned()
+    }
+
+    pub(crate) fn set_label(&self, id: WireId, label: Option<&str>) {
+        let mut labels = self.labels.lock().unwrap();
+        match label {
+            Some(label) => labels.insert(id, label.into()),
+            None => labels.remove(&id),
+        };
+    }
+}
@@ -0,0 +1,96 @@
+use crate::elements::error::BinariiError;
+use crate::elements::signal::Signal;
+
+const MAGIC: &[u8; 4] = b"BNS1";
+
+/// Saved values of a `Complex` hierarchy, see `Complex::snapshot`.
+///
+/// Wires are stored in the order of `Complex::nets` and components in compiled order, so a
+/// snapshot can be restored into any instance of the same template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub(crate) signals: Vec<Signal>,
+    pub(crate) components: Vec<Vec<u8>>,
+}
+
+impl Snapshot {
+    pub fn signals(&self) -> &[Signal] {
+        &self.signals
+    }
+
+    /// Encodes the snapshot as little-endian bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend((self.signals.len() as u64).to_le_bytes());
+        for signal in self.signals.iter() {
+            bytes.extend(signal.value.to_le_bytes());
+            bytes.extend(signal.unknown.to_le_bytes());
+        }
+        bytes.extend((self.components.len() as u64).to_le_bytes());
+        for state in self.components.iter() {
+            bytes.extend((state.len() as u64).to_le_bytes());
+            bytes.extend(state);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BinariiError> {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(BinariiError::InvalidSnapshot("unknown format"));
+        }
+
+        let len = reader.len()?;
+        let mut signals = Vec::new();
+        for _ in 0..len {
+            signals.push(Signal {
+                value: reader.u64()?,
+                unknown: reader.u64()?,
+            });
+        }
+
+        let len = reader.len()?;
+        let mut components = Vec::new();
+        for _ in 0..len {
+            let len = reader.len()?;
+            components.push(reader.take(len)?.to_vec());
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(BinariiError::InvalidSnapshot("trailing bytes"));
+        }
+        Ok(Self {
+            signals,
+            components,
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BinariiError> {
+        if len > self.bytes.len() {
+            return Err(BinariiError::InvalidSnapshot("unexpected end of data"));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u64(&mut self) -> Result<u64, BinariiError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Reads a count, rejecting values larger than the remaining data could hold.
+    fn len(&mut self) -> Result<usize, BinariiError> {
+        let len = self.u64()?;
+        if len > self.bytes.len() as u64 {
+            return Err(BinariiError::InvalidSnapshot("unexpected end of data"));
+        }
+        Ok(len as usize)
+    }
+}
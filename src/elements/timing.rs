@@ -0,0 +1,268 @@
+use crate::elements::complex::Complex;
+use crate::elements::error::BinariiError;
+use crate::elements::gate::Gate;
+use crate::elements::signal::{Signal, WireId};
+use crate::elements::wire::Wire;
+use bevy::utils::HashMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Event-queue simulation of the gates of a `Complex` using their propagation delays.
+///
+/// Wire values are changed through `set` and applied in time order, so short pulses on
+/// internal wires are kept in the trace instead of being hidden by a settled evaluation.
+/// Nets with several drivers are resolved like the shared nets of a `Complex`.
+/// Components have no propagation delay model, so a complex holding one is rejected.
+pub struct Timing {
+    gates: Vec<Gate>,
+    outputs: Vec<usize>,
+    wires: Vec<Wire>,
+    fanout: Vec<Vec<usize>>,
+    /// Per gate, the driver slot it writes when its output net has several drivers.
+    slots: Vec<Option<(usize, usize)>>,
+    /// Per shared net, the net index, the value of each driver and the fighting lanes.
+    shared: Vec<usize>,
+    drives: Vec<Vec<Signal>>,
+    fighting: Vec<u64>,
+    ids: HashMap<WireId, usize>,
+    queue: BinaryHeap<Reverse<Event>>,
+    seq: u64,
+    time: u64,
+    trace: Vec<Change>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Event {
+    time: u64,
+    seq: u64,
+    net: usize,
+    /// The gate that drives the value, or `None` for a value set from outside.
+    gate: Option<usize>,
+    value: Signal,
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.time, self.seq).cmp(&(other.time, other.seq))
+    }
+}
+
+/// A wire value change applied by the timing simulation.
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub time: u64,
+    pub wire: Wire,
+    pub value: Signal,
+}
+
+impl Timing {
+    pub fn new(complex: &Complex) -> Result<Self, BinariiError> {
+        complex.check_gates_only("timing")?;
+        let mut timing = Self {
+            gates: Vec::new(),
+            outputs: Vec::new(),
+            wires: Vec::new(),
+            fanout: Vec::new(),
+            slots: Vec::new(),
+            shared: Vec::new(),
+            drives: Vec::new(),
+            fighting: Vec::new(),
+            ids: HashMap::new(),
+            queue: BinaryHeap::new(),
+            seq: 0,
+            time: 0,
+            trace: Vec::new(),
+        };
+
+        let mut gates = Vec::new();
+        complex.for_each_gate(&mut |gate| gates.push(gate.clone()));
+        for (idx, gate) in gates.iter().enumerate() {
+            for wire in gate.inputs() {
+                let net = timing.net(wire.clone());
+                if !timing.fanout[net].contains(&idx) {
+                    timing.fanout[net].push(idx);
+                }
+            }
+            let out = timing.net(gate.get_out());
+            timing.outputs.push(out);
+        }
+        timing.gates = gates;
+
+        let mut drivers = vec![0; timing.wires.len()];
+        for &out in timing.outputs.iter() {
+            drivers[out] += 1;
+        }
+        let mut shared = HashMap::<usize, usize>::new();
+        for &out in timing.outputs.iter() {
+            if drivers[out] < 2 {
+                timing.slots.push(None);
+                continue;
+            }
+            let idx = *shared.entry(out).or_insert_with(|| {
+                timing.shared.push(out);
+                timing.drives.push(Vec::new());
+                timing.fighting.push(0);
+                timing.shared.len() - 1
+            });
+            timing.drives[idx].push(Signal::Z);
+            timing.slots.push(Some((idx, timing.drives[idx].len() - 1)));
+        }
+
+        for idx in 0..timing.gates.len() {
+            timing.evaluate(idx);
+        }
+        Ok(timing)
+    }
+
+    fn net(&mut self, wire: Wire) -> usize {
+        let wires = &mut self.wires;
+        let fanout = &mut self.fanout;
+        *self.ids.entry(wire.id()).or_insert_with(|| {
+            wires.push(wire);
+            fanout.push(Vec::new());
+            wires.len() - 1
+        })
+    }
+
+    fn schedule(&mut self, time: u64, net: usize, gate: Option<usize>, value: Signal) {
+        self.seq += 1;
+        self.queue.push(Reverse(Event {
+            time,
+            seq: self.seq,
+            net,
+            gate,
+            value,
+        }));
+    }
+
+    fn evaluate(&mut self, idx: usize) {
+        let gate = &self.gates[idx];
+        let value = gate
+            .kind()
+            .eval(gate.inputs().iter().map(|wire| wire.signal()));
+        let time = self.time + gate.delay() as u64;
+        self.schedule(time, self.outputs[idx], Some(idx), value);
+    }
+
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    /// Schedules `wire` to change to `value` at the current time.
+    pub fn set(&mut self, wire: &Wire, value: bool) {
+        self.set_lanes(wire, if value { u64::MAX } else { 0 });
+    }
+
+    pub fn set_lanes(&mut self, wire: &Wire, lanes: u64) {
+        self.set_signal(wire, Signal::known(lanes));
+    }
+
+    pub fn set_signal(&mut self, wire: &Wire, signal: Signal) {
+        let net = self.net(wire.clone());
+        self.schedule(self.time, net, None, signal);
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Applies all events up to and including `time` and advances the clock to it.
+    pub fn run_until(&mut self, time: u64) {
+        let mut touched = Vec::new();
+        while let Some(Reverse(event)) = self.queue.peek().copied() {
+            if event.time > time {
+                break;
+            }
+            self.time = event.time;
+
+            while let Some(Reverse(event)) = self.queue.peek().copied() {
+                if event.time != self.time {
+                    break;
+                }
+                self.queue.pop();
+                let value = self.resolve(event);
+                let wire = &self.wires[event.net];
+                if wire.raw_signal() == value {
+                    continue;
+                }
+                wire.set_signal(value);
+                self.trace.push(Change {
+                    time: self.time,
+                    wire: wire.clone(),
+                    value,
+                });
+                for &idx in self.fanout[event.net].iter() {
+                    if !touched.contains(&idx) {
+                        touched.push(idx);
+                    }
+                }
+            }
+
+            for idx in touched.drain(..) {
+                self.evaluate(idx);
+            }
+        }
+        self.time = self.time.max(time);
+    }
+
+    /// Runs until no events are left, returning the time of the last wire change, or `None`
+    /// if events are still pending after `max_time` units.
+    pub fn settle(&mut self, max_time: u64) -> Option<u64> {
+        let start = self.trace.len();
+        let deadline = self.time + max_time;
+        while let Some(Reverse(event)) = self.queue.peek().copied() {
+            if event.time > deadline {
+                return None;
+            }
+            self.run_until(event.time);
+        }
+        Some(
+            self.trace[start..]
+                .last()
+                .map(|change| change.time)
+                .unwrap_or(self.time),
+        )
+    }
+
+    /// Combines the value of a gate event with the other drivers of its net.
+    fn resolve(&mut self, event: Event) -> Signal {
+        let (shared, slot) = match event.gate.and_then(|gate| self.slots[gate]) {
+            Some(slot) => slot,
+            None => return event.value,
+        };
+        let drives = &mut self.drives[shared];
+        drives[slot] = event.value;
+        let (signal, fighting) = Signal::resolve(drives);
+        self.fighting[shared] = fighting;
+        signal
+    }
+
+    /// Returns the wires whose drivers currently fight.
+    pub fn contention(&self) -> Vec<Wire> {
+        self.shared
+            .iter()
+            .zip(self.fighting.iter())
+            .filter(|(_, fighting)| **fighting != 0)
+            .map(|(&net, _)| self.wires[net].clone())
+            .collect()
+    }
+
+    pub fn trace(&self) -> &[Change] {
+        &self.trace
+    }
+
+    pub fn clear_trace(&mut self) {
+        self.trace.clear();
+    }
+
+    /// Returns the changes of `wire` recorded in the trace.
+    pub fn changes<'a>(&'a self, wire: &'a Wire) -> impl Iterator<Item = &'a Change> + 'a {
+        self.trace.iter().filter(move |change| change.wire == *wire)
+    }
+}
@@ -1,30 +1,165 @@
-use std::cell::Cell;
+use crate::elements::signal::{Handle, Level, Pull, Signal, Signals, WireId, LANES};
+use bevy::utils::HashMap;
 use std::fmt::{Debug, Display};
-use std::rc::Rc;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+/// Maps the wires of a template onto fresh wires that start with the same signals.
+#[derive(Default)]
+pub(crate) struct WireMap {
+    wires: HashMap<WireId, Wire>,
+}
+
+impl WireMap {
+    pub(crate) fn get(&mut self, wire: &Wire) -> Wire {
+        self.wires
+            .entry(wire.id())
+            .or_insert_with(|| {
+                let copy = Wire::new();
+                copy.set_signal(wire.raw_signal());
+                if let Some(label) = wire.label() {
+                    copy.set_label(&label);
+                }
+                copy.set_pull(wire.pull());
+                copy
+            })
+            .clone()
+    }
+}
+
+/// Handle to a value in the global `Signals` store.
+///
+/// The value is freed when the last handle to it drops.
+#[derive(Clone)]
 pub struct Wire {
-    value: Rc<Cell<bool>>,
+    handle: Arc<Handle>,
 }
 
 impl Wire {
     pub fn new() -> Self {
         Self {
-            value: Rc::new(Cell::new(false)),
+            handle: Signals::global().alloc(),
         }
     }
 
+    /// Returns a handle to the wire `id` while some other handle keeps it alive.
+    pub fn from_id(id: WireId) -> Option<Self> {
+        Signals::global().handle(id).map(|handle| Self { handle })
+    }
+
+    /// Creates a wire carrying a human-readable label for debug output.
+    pub fn named(label: &str) -> Self {
+        let wire = Self::new();
+        wire.set_label(label);
+        wire
+    }
+
+    /// Labels are shared by every handle to the wire.
+    pub fn set_label(&self, label: &str) {
+        Signals::global().set_label(self.handle.id, Some(label));
+    }
+
+    pub fn clear_label(&self) {
+        Signals::global().set_label(self.handle.id, None);
+    }
+
+    pub fn label(&self) -> Option<String> {
+        Signals::global()
+            .label(self.handle.id)
+            .map(|label| label.to_string())
+    }
+
+    /// Returns the label, or the id when the wire has none.
+    pub fn name(&self) -> String {
+        self.label().unwrap_or_else(|| self.id().to_string())
+    }
+
+    /// Sets every lane to `value`.
+    #[inline]
     pub fn set(&self, value: bool) {
-        self.value.set(value);
+        self.set_lanes(if value { u64::MAX } else { 0 });
     }
 
+    /// Returns whether lane 0 is high; `X` and `Z` read as low.
+    #[inline]
     pub fn get(&self) -> bool {
-        self.value.get()
+        self.handle.chunk.value(self.handle.id) & 1 == 1
+    }
+
+    #[inline]
+    pub fn set_lanes(&self, lanes: u64) {
+        self.handle.chunk.set(self.handle.id, Signal::known(lanes));
+    }
+
+    #[inline]
+    pub fn lanes(&self) -> u64 {
+        self.handle.chunk.value(self.handle.id)
+    }
+
+    /// Panics when `lane` is not below `LANES`.
+    pub fn set_lane(&self, lane: usize, value: bool) {
+        check_lane(lane);
+        self.handle.chunk.set_lane(self.handle.id, lane, value);
+    }
+
+    pub fn get_lane(&self, lane: usize) -> bool {
+        check_lane(lane);
+        (self.lanes() >> lane) & 1 == 1
+    }
+
+    #[inline]
+    pub fn signal(&self) -> Signal {
+        self.handle.chunk.get(self.handle.id)
     }
 
-    pub fn id(&self) -> usize {
-        Rc::as_ptr(&self.value) as *const () as usize
+    /// Returns the signal as driven, ignoring the pull.
+    #[inline]
+    pub(crate) fn raw_signal(&self) -> Signal {
+        self.handle.chunk.raw(self.handle.id)
     }
+
+    #[inline]
+    pub fn set_signal(&self, signal: Signal) {
+        self.handle.chunk.set(self.handle.id, signal);
+    }
+
+    /// Returns the level of lane 0.
+    pub fn level(&self) -> Level {
+        self.signal().level(0)
+    }
+
+    /// Sets every lane to `level`.
+    pub fn set_level(&self, level: Level) {
+        self.set_signal(Signal::splat(level));
+    }
+
+    /// Gives the wire a weak level that reads replace its `Z` lanes with.
+    ///
+    /// The stored signal keeps the `Z` lanes, so a pulled wire never fights its drivers.
+    pub fn set_pull(&self, pull: Option<Pull>) {
+        self.handle.chunk.set_pull(self.handle.id, pull);
+    }
+
+    pub fn pull_up(&self) {
+        self.set_pull(Some(Pull::Up));
+    }
+
+    pub fn pull_down(&self) {
+        self.set_pull(Some(Pull::Down));
+    }
+
+    #[inline]
+    pub fn pull(&self) -> Option<Pull> {
+        self.handle.chunk.pull(self.handle.id)
+    }
+
+    pub fn id(&self) -> WireId {
+        self.handle.id
+    }
+}
+
+fn check_lane(lane: usize) {
+    assert!(lane < LANES, "lane {} out of {}", lane, LANES);
 }
 
 impl PartialEq for Wire {
@@ -33,18 +168,37 @@ impl PartialEq for Wire {
     }
 }
 
+impl Eq for Wire {}
+
+impl Hash for Wire {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
 impl Default for Wire {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Prints `label=value`, or only the value for a wire without a label.
+impl Debug for Wire {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.label() {
+            Some(label) => write!(f, "{}={}", label, self),
+            None => write!(f, "{}", self),
+        }
+    }
+}
+
 impl Display for Wire {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.get() {
-            write!(f, "1")
-        } else {
-            write!(f, "0")
+        match self.level() {
+            Level::Low => write!(f, "0"),
+            Level::High => write!(f, "1"),
+            Level::Z => write!(f, "z"),
+            Level::X => write!(f, "x"),
         }
     }
 }
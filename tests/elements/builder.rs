@@ -0,0 +1,98 @@
+use crate::elements::complex::sum;
+use binarii::elements::bus::{Bus, BusAccess};
+use binarii::elements::complex::Complex;
+use binarii::elements::gate::Gate;
+use binarii::elements::wire::Wire;
+use binarii::elements::Conduct;
+
+pub fn byte_sum() -> Complex {
+    let mut sum_block = Complex::builder("byte_sum");
+    let a = Bus::new(8);
+    let b = Bus::new(8);
+    let res = Bus::new(9);
+
+    let mut carry = Wire::new();
+    sum_block.add_input_bus(a.clone());
+    sum_block.add_input_bus(b.clone());
+    sum_block.add_input(carry.clone());
+    sum_block.add_output_bus(res.clone());
+
+    for i in (0..8).rev() {
+        let mut bit = sum();
+        bit.set_in(0, a.get_wire(i));
+        bit.set_in(1, b.get_wire(i));
+        bit.set_in(2, carry);
+        bit.set_out(0, res.get_wire(i));
+        carry = bit.get_out(1);
+        sum_block.add_complex(bit);
+    }
+
+    sum_block.set_out(8, carry.clone());
+    sum_block.finish()
+}
+
+#[test]
+pub fn test_builder_byte_sum() {
+    let sum = byte_sum();
+    let a = sum.get_in_bus(0, 8);
+    let b = sum.get_in_bus(8, 8);
+    let res = sum.get_out_bus(0, 8);
+    let carry_out = sum.get_out(8);
+
+    for (i, j) in [(0u8, 0u8), (1, 1), (100, 155), (255, 1), (200, 100)] {
+        a.set(0, i);
+        b.set(0, j);
+        sum.conduct();
+        let res: u8 = res.get(0);
+        let (expected_sum, expected_carry) = i.overflowing_add(j);
+        assert_eq!(res, expected_sum);
+        assert_eq!(carry_out.get(), expected_carry);
+    }
+}
+
+#[test]
+pub fn test_builder_set_in() {
+    let mut builder = Complex::builder("and_3");
+    let wires = (0..3).map(|_| Wire::new()).collect::<Vec<_>>();
+    for wire in wires.iter() {
+        builder.add_input(wire.clone());
+    }
+    let inner = Wire::new();
+    builder.add_gate(Gate::and(inner.clone(), wires[2].clone(), Wire::new()));
+    let first = builder.add_gate(Gate::and(wires[0].clone(), wires[1].clone(), inner));
+
+    let mut inner_and = Complex::new("and");
+    let (x, y, out) = (Wire::new(), Wire::new(), Wire::new());
+    inner_and.add_input(x.clone());
+    inner_and.add_input(y.clone());
+    inner_and.add_output(out.clone());
+    inner_and.add_gate(Gate::and(x, y, out));
+    builder.add_complex(inner_and);
+
+    let a = Wire::new();
+    builder.set_in(0, a.clone());
+    assert_eq!(builder.get_in(0), a);
+    let complex = builder.finish();
+    assert_eq!(complex.get_in(0), a);
+    assert_eq!(complex.element(first).unwrap().input()[0], a);
+}
+
+#[test]
+pub fn test_builder_long_chain() {
+    const LEN: usize = 5000;
+    let mut builder = Complex::builder("chain");
+    let wires = (0..=LEN).map(|_| Wire::new()).collect::<Vec<_>>();
+    builder.add_input(wires[0].clone());
+    builder.add_output(wires[LEN].clone());
+    for i in (0..LEN).rev() {
+        builder.add_gate(Gate::not(wires[i].clone(), wires[i + 1].clone()));
+    }
+    let chain = builder.finish();
+
+    chain.get_in(0).set(true);
+    chain.conduct();
+    assert_eq!(chain.get_out(0).get(), true);
+    chain.get_in(0).set(false);
+    chain.conduct();
+    assert_eq!(chain.get_out(0).get(), false);
+}
@@ -1,4 +1,5 @@
-use binarii::elements::bus::{Bus, BusAccess};
+use binarii::elements::bits::Bits;
+use binarii::elements::bus::{BitOrder, Bus, BusAccess, LaneAccess};
 
 #[test]
 pub fn test_set_u8() {
@@ -25,3 +26,131 @@ pub fn test_set_u8() {
     let val: bool = bus.get(7);
     assert_eq!(val, false);
 }
+
+#[test]
+pub fn test_lanes_u8() {
+    let bus = Bus::new(8);
+    let values = (0..64u8).map(|i| i * 3).collect::<Vec<_>>();
+    bus.set_lanes(0, &values);
+    let lanes: [u8; 64] = bus.get_lanes(0);
+    assert_eq!(&lanes[..], &values[..]);
+
+    let b: u8 = bus.get(0);
+    assert_eq!(b, 0);
+    assert_eq!(bus.get_wire(7).lanes(), 0xaaaa_aaaa_aaaa_aaaa);
+
+    let flags: [bool; 64] = bus.get_lanes(6);
+    assert_eq!(flags[0], false);
+    assert_eq!(flags[1], true);
+}
+
+#[test]
+pub fn test_wide_integers() {
+    let bus = Bus::new(32);
+    bus.set(0, 0xdead_beefu32);
+    let value: u32 = bus.get(0);
+    assert_eq!(value, 0xdead_beef);
+    let high: u16 = bus.get(0);
+    assert_eq!(high, 0xdead);
+    let low: u16 = bus.get(16);
+    assert_eq!(low, 0xbeef);
+    let top: u8 = bus.get(0);
+    assert_eq!(top, 0xde);
+
+    bus.set(16, -2i16);
+    let value: i16 = bus.get(16);
+    assert_eq!(value, -2);
+    let value: u16 = bus.get(16);
+    assert_eq!(value, 0xfffe);
+
+    assert!(BusAccess::<u64>::try_set(&bus, 0, 1).is_err());
+    let bus = Bus::new(128);
+    bus.set(0, i128::MIN + 5);
+    let value: i128 = bus.get(0);
+    assert_eq!(value, i128::MIN + 5);
+}
+
+#[test]
+pub fn test_lanes_signed() {
+    let bus = Bus::new(16);
+    let values = (0..64i16).map(|i| i * -300).collect::<Vec<_>>();
+    bus.set_lanes(0, &values);
+    let lanes: [i16; 64] = bus.get_lanes(0);
+    assert_eq!(&lanes[..], &values[..]);
+}
+
+#[test]
+pub fn test_bits() {
+    let bus = Bus::new(100);
+    let mut bits = Bits::new(100);
+    bits.set(0, true);
+    bits.set(70, true);
+    bits.set(99, true);
+    bus.set(0, bits.clone());
+    assert_eq!(bus.get_wire(70).get(), true);
+    assert_eq!(bus.get_wire(71).get(), false);
+    let read: Bits = bus.get(0);
+    assert_eq!(read, bits);
+
+    let tail: Bits = bus.get(98);
+    assert_eq!(tail.to_string(), "01");
+    let value: u8 = bus.get(0);
+    assert_eq!(value, 0b10000000);
+
+    assert!(bus.try_set(50, bits).is_err());
+    assert!(BusAccess::<Bits>::try_get(&bus, 100).unwrap().is_empty());
+    assert!(BusAccess::<Bits>::try_get(&bus, 101).is_err());
+
+    let bits = Bits::from(&[true, false, true][..]);
+    assert_eq!(bits.len(), 3);
+    assert_eq!(bits.iter().collect::<Vec<_>>(), [true, false, true]);
+}
+
+#[test]
+pub fn test_short_bus() {
+    let bus = Bus::new(4);
+    bus.set(0, 0b1010_0110u8);
+    assert_eq!(bus.to_string(), "1010");
+    let value: u8 = bus.get(0);
+    assert_eq!(value, 0b1010);
+    assert!(bus.try_set(0, 0b1010_0110u8).is_err());
+    bus.set_lanes(0, &[0b0101_0000u8]);
+    assert_eq!(bus.to_string(), "0101");
+
+    bus.set(0, 0b0110u16);
+    assert_eq!(bus.to_string(), "0110");
+
+    bus.set(0, -3i32);
+    let value: i32 = bus.get(0);
+    assert_eq!(value, -3);
+    let value: u32 = bus.get(0);
+    assert_eq!(value, 0b1101);
+}
+
+#[test]
+pub fn test_lsb_first() {
+    let bus = Bus::new(16).with_order(BitOrder::LsbFirst);
+    bus.set(0, 0b0000_0110u8);
+    assert_eq!(bus.to_string(), "0110000000000000");
+    let value: u8 = bus.get(0);
+    assert_eq!(value, 6);
+
+    bus.set(0, -2i16);
+    assert_eq!(bus.get_wire(0).get(), false);
+    assert_eq!(bus.get_wire(15).get(), true);
+    let value: i16 = bus.get(0);
+    assert_eq!(value, -2);
+
+    let values = (0..64u16).map(|i| i * 1000).collect::<Vec<_>>();
+    bus.set_lanes(0, &values);
+    let lanes: [u16; 64] = bus.get_lanes(0);
+    assert_eq!(&lanes[..], &values[..]);
+    assert_eq!(bus.get_wire(0).lanes(), 0);
+
+    let mut msb = bus.clone();
+    msb.set_order(BitOrder::MsbFirst);
+    assert_eq!(bus.order(), BitOrder::LsbFirst);
+    bus.set(0, 1u16);
+    let value: u16 = msb.get(0);
+    assert_eq!(value, 0x8000);
+}
@@ -1,6 +1,9 @@
-use binarii::elements::bus::{Bus, BusAccess};
-use binarii::elements::complex::Complex;
+use binarii::elements::bus::{BitOrder, Bus, BusAccess, LaneAccess};
+use binarii::elements::complex::{Complex, Element, Mode};
+use binarii::elements::error::BinariiError;
 use binarii::elements::gate::Gate;
+use binarii::elements::oscillator::Oscillator;
+use binarii::elements::signal::Level;
 use binarii::elements::wire::Wire;
 use binarii::elements::Conduct;
 
@@ -59,6 +62,32 @@ pub fn rs_flip_flop() -> Complex {
     complex
 }
 
+#[test]
+pub fn test_undriven_input_iterations() {
+    let (a, spare, out) = (Wire::new(), Wire::named("spare"), Wire::new());
+    let mut complex = Complex::new("and");
+    complex.add_input(a.clone());
+    complex.add_output(out.clone());
+    complex.add_gate(Gate::and(a, spare, out));
+    assert!(format!("{:?}", complex).ends_with("ITERS:2\n"));
+    assert!(format!("{:?}", rs_flip_flop()).ends_with("ITERS:2\n"));
+}
+
+#[test]
+pub fn test_placed_driver_iterations() {
+    assert!(format!("{:?}", byte_sum()).ends_with("ITERS:1\n"));
+
+    const LEN: usize = 100;
+    let wires = (0..=LEN).map(|_| Wire::new()).collect::<Vec<_>>();
+    let mut chain = Complex::new("chain");
+    chain.add_input(wires[0].clone());
+    chain.add_output(wires[LEN].clone());
+    for i in 0..LEN {
+        chain.add_gate(Gate::not(wires[i].clone(), wires[i + 1].clone()));
+    }
+    assert!(format!("{:?}", chain).ends_with("ITERS:1\n"));
+}
+
 #[test]
 pub fn test_rs_flip_flop() {
     let rs = rs_flip_flop();
@@ -94,6 +123,119 @@ pub fn test_rs_flip_flop() {
     assert_eq!(qn.get(), true);
 }
 
+#[test]
+pub fn test_rs_flip_flop_settle() {
+    let rs = rs_flip_flop();
+    let set = rs.get_in(0);
+    let q = rs.get_out(0);
+    let qn = rs.get_out(1);
+
+    set.set(true);
+    assert!(rs.settle().is_ok());
+    assert_eq!(q.get(), true);
+    assert_eq!(qn.get(), false);
+
+    set.set(false);
+    assert_eq!(rs.settle().unwrap(), 1);
+    assert_eq!(q.get(), true);
+    assert_eq!(qn.get(), false);
+}
+
+pub fn ring_oscillator() -> Complex {
+    let mut ring = Complex::new("ring_oscillator");
+    let a = Wire::new();
+    let b = Wire::new();
+    let c = Wire::new();
+    ring.add_output(a.clone());
+    ring.add_gate(Gate::not(a.clone(), b.clone()));
+    ring.add_gate(Gate::not(b.clone(), c.clone()));
+    ring.add_gate(Gate::not(c.clone(), a.clone()));
+    ring
+}
+
+#[test]
+pub fn test_ring_oscillator_settle() {
+    let mut ring = ring_oscillator();
+    ring.set_settle_limit(16);
+    let err = ring.settle().unwrap_err();
+    assert_eq!(err.complex, "ring_oscillator");
+    assert_eq!(err.iterations, 16);
+    assert_eq!(err.wires.len(), 3);
+    assert!(err.wires.contains(&ring.get_out(0)));
+    assert!(err.names.contains(&"n0".to_string()));
+
+    let mut other = ring_oscillator();
+    other.set_settle_limit(16);
+    assert_eq!(other.settle().unwrap_err().to_string(), err.to_string());
+}
+
+#[test]
+pub fn test_rs_flip_flop_analysis() {
+    let mut rs = rs_flip_flop();
+    let analysis = rs.compile();
+    assert_eq!(analysis.feedback.len(), 1);
+
+    let feedback = &analysis.feedback[0];
+    assert_eq!(feedback.path, vec!["rs_flip_flop"]);
+    assert_eq!(feedback.elements, rs.element_ids().to_vec());
+    assert_eq!(feedback.kinds, vec!["nor", "nor"]);
+    assert_eq!(feedback.wires.len(), 2);
+    assert!(feedback.wires.contains(&rs.get_out(0)));
+    assert!(feedback.wires.contains(&rs.get_out(1)));
+    let mut names = feedback.names.clone();
+    names.sort();
+    assert_eq!(names, ["n2", "n3"]);
+    assert_eq!(
+        feedback.to_string(),
+        rs_flip_flop().compile().feedback[0].to_string()
+    );
+}
+
+#[test]
+pub fn test_nested_feedback_analysis() {
+    let mut flip_flop = byte_flip_flop();
+    let analysis = flip_flop.compile();
+    assert_eq!(analysis.feedback.len(), 8);
+    for feedback in analysis.feedback.iter() {
+        assert_eq!(
+            feedback.path,
+            vec!["byte_flip_flop", "d_flip_flop", "rs_flip_flop"]
+        );
+    }
+
+    let mut ring = ring_oscillator();
+    let analysis = ring.compile();
+    assert_eq!(analysis.feedback.len(), 1);
+    assert_eq!(analysis.feedback[0].kinds, vec!["not", "not", "not"]);
+
+    assert!(!byte_sum().analysis().has_feedback());
+}
+
+#[test]
+pub fn test_pass_through_analysis() {
+    let mut two_bufs = Complex::new("two_bufs");
+    let (a, b, c, d) = (Wire::new(), Wire::new(), Wire::new(), Wire::new());
+    two_bufs.add_input(a.clone());
+    two_bufs.add_input(c.clone());
+    two_bufs.add_output(b.clone());
+    two_bufs.add_output(d.clone());
+    two_bufs.add_gate(Gate::buf(a.clone(), b.clone()));
+    two_bufs.add_gate(Gate::buf(c.clone(), d.clone()));
+
+    let mut complex = Complex::new("outer");
+    complex.add_input(a.clone());
+    complex.add_output(d.clone());
+    complex.add_complex(two_bufs);
+    complex.add_gate(Gate::not(b, c.clone()));
+    let analysis = complex.compile();
+    assert!(!analysis.has_feedback(), "{}", analysis);
+
+    complex.add_gate(Gate::not(d, a));
+    let analysis = complex.compile();
+    assert_eq!(analysis.feedback.len(), 1);
+    assert_eq!(analysis.feedback[0].kinds, vec!["two_bufs", "not", "not"]);
+}
+
 #[test]
 pub fn test_not() {
     let complex = not();
@@ -175,6 +317,31 @@ pub fn test_and_3() {
     assert_eq!(and_3.get_out(0).get(), false);
 }
 
+#[test]
+pub fn test_and_3_gate() {
+    let wires = (0..3).map(|_| Wire::new()).collect::<Vec<_>>();
+    let mut and_3 = Complex::new("and_3");
+    and_3.add_input_bus(Bus::with_wires(wires.clone()));
+    and_3.add_output(Wire::new());
+    and_3.add_gate(Gate::and_n(wires.clone(), and_3.get_out(0)));
+    assert_eq!(and_3.flatten().element_ids().len(), 1);
+
+    for value in 0..8u8 {
+        for (i, wire) in wires.iter().enumerate() {
+            wire.set(value >> i & 1 == 1);
+        }
+        and_3.conduct();
+        assert_eq!(and_3.get_out(0).get(), value == 7);
+    }
+
+    wires[1].set_level(Level::X);
+    and_3.conduct();
+    assert_eq!(and_3.get_out(0).level(), Level::X);
+    wires[0].set(false);
+    and_3.conduct();
+    assert_eq!(and_3.get_out(0).level(), Level::Low);
+}
+
 pub fn half_sum() -> Complex {
     let mut complex = Complex::new("half_sum");
     let wire_a = Wire::new();
@@ -333,6 +500,99 @@ pub fn test_byte_sum() {
     }
 }
 
+#[test]
+pub fn test_byte_sum_event_driven() {
+    let mut sum = byte_sum();
+    sum.set_mode(Mode::EventDriven);
+    assert_eq!(sum.mode(), Mode::EventDriven);
+    let a = sum.get_in_bus(0, 8);
+    let b = sum.get_in_bus(8, 8);
+    let carry_in = sum.get_in(16);
+    let res = sum.get_out_bus(0, 8);
+    let carry_out = sum.get_out(8);
+
+    for i in 0..255u8 {
+        for j in 0..255u8 {
+            a.set(0, i);
+            b.set(0, j);
+            carry_in.set(false);
+            sum.conduct();
+            let res: u8 = res.get(0);
+            let carry = carry_out.get();
+            let (expected_sum, expected_carry) = i.overflowing_add(j);
+            assert_eq!(res, expected_sum);
+            assert_eq!(carry, expected_carry);
+        }
+    }
+}
+
+#[test]
+pub fn test_byte_sum_lanes() {
+    let sum = byte_sum();
+    let a = sum.get_in_bus(0, 8);
+    let b = sum.get_in_bus(8, 8);
+    let carry_in = sum.get_in(16);
+    let res = sum.get_out_bus(0, 8);
+    let carry_out = sum.get_out(8);
+
+    carry_in.set(false);
+    for i in 0..=255u8 {
+        for j in (0..256).step_by(64) {
+            let operands = (j..j + 64).map(|j| j as u8).collect::<Vec<_>>();
+            a.set_lanes(0, &[i; 64]);
+            b.set_lanes(0, &operands);
+            sum.conduct();
+
+            let results: [u8; 64] = res.get_lanes(0);
+            for (lane, j) in operands.iter().enumerate() {
+                let (expected_sum, expected_carry) = i.overflowing_add(*j);
+                assert_eq!(results[lane], expected_sum);
+                assert_eq!(carry_out.get_lane(lane), expected_carry);
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_byte_sum_unknown() {
+    let sum = byte_sum();
+    sum.set_unknown();
+    sum.get_in_bus(0, 8).set(0, 3u8);
+    sum.get_in_bus(8, 8).set(0, 5u8);
+    sum.conduct();
+    let res = sum.get_out_bus(0, 8);
+    assert_eq!(res.get_wire(7).level(), Level::X);
+
+    sum.get_in(16).set(false);
+    sum.conduct();
+    assert!(res.wires().iter().all(|w| w.level() != Level::X));
+    let value: u8 = res.get(0);
+    assert_eq!(value, 8);
+}
+
+#[test]
+pub fn test_byte_sum_on_thread() {
+    let sum = byte_sum();
+    let res = sum.get_out_bus(0, 8);
+    let handle = std::thread::spawn(move || {
+        sum.get_in_bus(0, 8).set(0, 100u8);
+        sum.get_in_bus(8, 8).set(0, 55u8);
+        sum.conduct();
+        sum
+    });
+    let sum = handle.join().unwrap();
+    let value: u8 = res.get(0);
+    assert_eq!(value, 155);
+
+    let copy = sum.instantiate();
+    copy.get_in_bus(0, 8).set(0, 1u8);
+    copy.conduct();
+    let value: u8 = copy.get_out_bus(0, 8).get(0);
+    assert_eq!(value, 56);
+    let value: u8 = sum.get_out_bus(0, 8).get(0);
+    assert_eq!(value, 155);
+}
+
 pub fn d_flip_flop() -> Complex {
     let mut flip_flop = Complex::new("d_flip_flop");
     let d = Wire::new();
@@ -402,6 +662,39 @@ pub fn test_d_flip_flop() {
     assert_eq!(nq.get(), true);
 }
 
+#[test]
+pub fn test_d_flip_flop_unknown() {
+    let flip_flop = d_flip_flop();
+    let d = flip_flop.get_in(0);
+    let clk = flip_flop.get_in(1);
+    let q = flip_flop.get_out(0);
+    let nq = flip_flop.get_out(1);
+
+    flip_flop.set_unknown();
+    assert_eq!(d.level(), Level::Z);
+    assert_eq!(q.level(), Level::X);
+
+    clk.set(false);
+    flip_flop.conduct();
+    assert_eq!(q.level(), Level::X);
+    assert_eq!(nq.level(), Level::X);
+
+    clk.set(true);
+    flip_flop.conduct();
+    assert_eq!(q.level(), Level::X);
+
+    d.set(true);
+    flip_flop.conduct();
+    assert_eq!(q.level(), Level::High);
+    assert_eq!(nq.level(), Level::Low);
+
+    clk.set(false);
+    d.set_level(Level::X);
+    flip_flop.conduct();
+    assert_eq!(q.level(), Level::High);
+    assert_eq!(nq.level(), Level::Low);
+}
+
 pub fn byte_flip_flop() -> Complex {
     let mut flip_flop = Complex::new("byte_flip_flop");
 
@@ -468,3 +761,437 @@ pub fn test_byte_flip_flop() {
     let data: u8 = q_out.get(0);
     assert_eq!(data, 0b10101010);
 }
+
+#[test]
+pub fn test_byte_flip_flop_event_driven() {
+    let iterative = byte_flip_flop();
+    let mut event_driven = byte_flip_flop();
+    event_driven.set_mode(Mode::EventDriven);
+
+    let steps: [(u8, bool); 8] = [
+        (0, true),
+        (0, false),
+        (0b11111111, false),
+        (0b11111111, true),
+        (0b11111111, false),
+        (0b10101010, false),
+        (0b10101010, true),
+        (0b01010101, false),
+    ];
+
+    for (data, clk) in steps {
+        for flip_flop in [&iterative, &event_driven] {
+            flip_flop.get_in_bus(0, 8).set(0, data);
+            flip_flop.get_in(8).set(clk);
+            flip_flop.conduct();
+        }
+        let expected: u8 = iterative.get_out_bus(0, 8).get(0);
+        let actual: u8 = event_driven.get_out_bus(0, 8).get(0);
+        assert_eq!(actual, expected);
+    }
+}
+
+pub fn shared_bus() -> Complex {
+    let mut complex = Complex::new("shared_bus");
+    let a = Bus::new(8);
+    let b = Bus::new(8);
+    let enable_a = Wire::new();
+    let enable_b = Wire::new();
+    let shared = Bus::new(8);
+
+    complex.add_input_bus(a.clone());
+    complex.add_input_bus(b.clone());
+    complex.add_input(enable_a.clone());
+    complex.add_input(enable_b.clone());
+    complex.add_output_bus(shared.clone());
+
+    for i in 0..8 {
+        complex.add_gate(Gate::tri_state(
+            a.get_wire(i),
+            enable_a.clone(),
+            shared.get_wire(i),
+        ));
+        complex.add_gate(Gate::tri_state(
+            b.get_wire(i),
+            enable_b.clone(),
+            shared.get_wire(i),
+        ));
+    }
+    complex
+}
+
+#[test]
+pub fn test_shared_bus() {
+    for mode in [Mode::Iterative, Mode::EventDriven] {
+        let mut complex = shared_bus();
+        complex.set_mode(mode);
+        let a = complex.get_in_bus(0, 8);
+        let b = complex.get_in_bus(8, 8);
+        let enable_a = complex.get_in(16);
+        let enable_b = complex.get_in(17);
+        let shared = complex.get_out_bus(0, 8);
+
+        a.set(0, 0b11111111u8);
+        b.set(0, 0b10101010u8);
+        complex.conduct();
+        assert_eq!(shared.get_wire(0).level(), Level::Z);
+        assert!(complex.contention().is_empty());
+
+        enable_a.set(true);
+        complex.conduct();
+        let value: u8 = shared.get(0);
+        assert_eq!(value, 0b11111111);
+
+        enable_a.set(false);
+        enable_b.set(true);
+        complex.conduct();
+        let value: u8 = shared.get(0);
+        assert_eq!(value, 0b10101010);
+        assert!(complex.contention().is_empty());
+
+        enable_a.set(true);
+        complex.conduct();
+        let contention = complex.contention();
+        assert_eq!(contention.len(), 4);
+        for i in 0..8 {
+            let wire = shared.get_wire(i);
+            if i % 2 == 0 {
+                assert_eq!(wire.level(), Level::High);
+            } else {
+                assert_eq!(wire.level(), Level::X);
+                assert!(contention.contains(&wire));
+            }
+        }
+        assert!(complex.settle().is_ok());
+        assert_eq!(complex.contention().len(), 4);
+
+        enable_b.set(false);
+        complex.conduct();
+        assert!(complex.contention().is_empty());
+        let value: u8 = shared.get(0);
+        assert_eq!(value, 0b11111111);
+    }
+}
+
+#[test]
+pub fn test_pulled_bus() {
+    for mode in [Mode::Iterative, Mode::EventDriven] {
+        let mut complex = shared_bus();
+        complex.set_mode(mode);
+        let a = complex.get_in_bus(0, 8);
+        let enable_a = complex.get_in(16);
+        let shared = complex.get_out_bus(0, 8);
+        for i in 0..8 {
+            if i % 2 == 0 {
+                shared.get_wire(i).pull_up();
+            } else {
+                shared.get_wire(i).pull_down();
+            }
+        }
+
+        complex.conduct();
+        let value: u8 = shared.get(0);
+        assert_eq!(value, 0b10101010);
+        assert!(complex.contention().is_empty());
+
+        a.set(0, 0b00001111u8);
+        enable_a.set(true);
+        complex.conduct();
+        let value: u8 = shared.get(0);
+        assert_eq!(value, 0b00001111);
+        assert!(complex.contention().is_empty());
+
+        enable_a.set(false);
+        complex.conduct();
+        let value: u8 = shared.get(0);
+        assert_eq!(value, 0b10101010);
+    }
+}
+
+#[test]
+pub fn test_edit_elements() {
+    let mut complex = Complex::new("edit");
+    let a = Wire::new();
+    let b = Wire::new();
+    let s = Wire::new();
+    let c = Wire::new();
+    complex.add_input(a.clone());
+    complex.add_input(b.clone());
+    complex.add_output(s.clone());
+    complex.add_output(c.clone());
+
+    let xor = complex.add_gate(Gate::xor(a.clone(), b.clone(), s.clone()));
+    let and = complex.add_gate(Gate::and(a.clone(), b.clone(), c.clone()));
+    assert_eq!(complex.element(xor).unwrap().tp(), "xor");
+
+    let old = complex.replace_element(
+        and,
+        Element::Gate(Gate::or(a.clone(), b.clone(), c.clone())),
+    );
+    assert_eq!(old.unwrap().tp(), "and");
+    assert_eq!(complex.element(and).unwrap().tp(), "or");
+    a.set(true);
+    b.set(false);
+    complex.conduct();
+    assert_eq!(s.get(), true);
+    assert_eq!(c.get(), true);
+
+    assert_eq!(complex.remove_element(xor).unwrap().tp(), "xor");
+    assert!(complex.element(xor).is_none());
+    assert!(complex.remove_element(xor).is_none());
+    assert_eq!(complex.element_ids(), &[and]);
+}
+
+#[test]
+pub fn test_disconnect() {
+    let mut complex = half_sum();
+    let a = complex.get_in(0);
+    let b = complex.get_in(1);
+    let c = complex.get_out(1);
+    let and = complex
+        .element_ids()
+        .iter()
+        .copied()
+        .find(|id| complex.element(*id).unwrap().tp() == "and")
+        .unwrap();
+
+    assert_eq!(complex.disconnect(and, &c), true);
+    assert_eq!(complex.disconnect(and, &c), false);
+    let gate = complex.element(and).unwrap();
+    assert_eq!(gate.input(), vec![a.clone(), b.clone()]);
+    assert_ne!(gate.output()[0], c);
+
+    c.set(false);
+    a.set(true);
+    b.set(true);
+    complex.conduct();
+    assert_eq!(c.get(), false);
+    assert_eq!(complex.get_out(0).get(), false);
+}
+
+#[test]
+pub fn test_instantiate() {
+    let template = sum();
+    let mut sum_block = Complex::new("byte_sum");
+    let a = Bus::new(8);
+    let b = Bus::new(8);
+    let res = Bus::new(8);
+    sum_block.add_input_bus(a.clone());
+    sum_block.add_input_bus(b.clone());
+    sum_block.add_output_bus(res.clone());
+
+    let mut carry = Wire::new();
+    for i in (0..8).rev() {
+        let mut bit = template.instantiate();
+        bit.set_in(0, a.get_wire(i));
+        bit.set_in(1, b.get_wire(i));
+        bit.set_in(2, carry);
+        bit.set_out(0, res.get_wire(i));
+        carry = bit.get_out(1);
+        sum_block.add_complex(bit);
+    }
+
+    for (i, j) in [(1u8, 2u8), (100, 27), (255, 1), (170, 85)] {
+        a.set(0, i);
+        b.set(0, j);
+        sum_block.conduct();
+        let sum: u8 = res.get(0);
+        assert_eq!(sum, i.wrapping_add(j));
+    }
+
+    template.get_in(0).set(true);
+    template.get_in(1).set(false);
+    template.get_in(2).set(false);
+    template.conduct();
+    assert_eq!(template.get_out(0).get(), true);
+    let copy = template.instantiate();
+    assert_ne!(copy.get_in(0), template.get_in(0));
+    assert_eq!(copy.get_out(0).get(), true);
+}
+
+#[test]
+pub fn test_oscillator_component() {
+    let clk = Wire::new();
+    let out = Wire::new();
+    let mut complex = Complex::new("blinker");
+    complex.add_output(out.clone());
+    let osc = complex.add_component(Oscillator::new(clk.clone(), 1));
+    complex.add_gate(Gate::not(clk.clone(), out.clone()));
+    assert_eq!(complex.element(osc).unwrap().tp(), "oscillator");
+    assert_eq!(complex.element(osc).unwrap().output(), vec![clk.clone()]);
+
+    complex.conduct();
+    assert_eq!(clk.get(), true);
+    assert_eq!(out.get(), false);
+    complex.conduct();
+    assert_eq!(clk.get(), false);
+    assert_eq!(out.get(), true);
+
+    complex.set_mode(Mode::EventDriven);
+    complex.conduct();
+    assert_eq!(clk.get(), true);
+    assert_eq!(out.get(), false);
+    complex.conduct();
+    assert_eq!(clk.get(), false);
+    assert_eq!(out.get(), true);
+
+    let copy = complex.instantiate();
+    copy.conduct();
+    assert_eq!(copy.get_out(0).get(), false);
+    assert_eq!(out.get(), true);
+}
+
+/// A sub-complex with an oscillator next to an RS latch, which takes two iterations per tick.
+fn clocked_latch() -> (Complex, Wire) {
+    let clk = Wire::new();
+    let mut inner = rs_flip_flop();
+    inner.add_output(clk.clone());
+    inner.add_component(Oscillator::new(clk.clone(), 1));
+
+    let mut outer = Complex::new("clocked");
+    outer.add_output(clk.clone());
+    outer.add_complex(inner);
+    (outer, clk)
+}
+
+#[test]
+pub fn test_oscillator_once_per_tick() {
+    for mode in [Mode::Iterative, Mode::EventDriven] {
+        let (mut complex, clk) = clocked_latch();
+        complex.set_mode(mode);
+        let mut trace = Vec::new();
+        for _ in 0..4 {
+            complex.conduct();
+            trace.push(clk.get());
+        }
+        assert_eq!(trace, [true, false, true, false]);
+
+        assert_eq!(complex.settle().unwrap(), 1);
+        assert_eq!(clk.get(), true);
+        assert!(complex.settle().is_ok());
+        assert_eq!(clk.get(), false);
+    }
+}
+
+pub fn named_byte_sum() -> Complex {
+    let sum = byte_sum();
+    let mut named = Complex::new("byte_sum");
+    named
+        .add_named_input_bus("a", sum.get_in_bus(0, 8))
+        .unwrap();
+    named
+        .add_named_input_bus("b", sum.get_in_bus(8, 8))
+        .unwrap();
+    named.add_named_input("cin", sum.get_in(16)).unwrap();
+    named
+        .add_named_output_bus("s", sum.get_out_bus(0, 8))
+        .unwrap();
+    named.add_named_output("cout", sum.get_out(8)).unwrap();
+    named.add_complex(sum);
+    named
+}
+
+#[test]
+pub fn test_named_ports() {
+    let sum = named_byte_sum();
+    let a = sum.get_in_bus_by_name("a").unwrap();
+    let b = sum.get_in_bus_by_name("b").unwrap();
+    let cin = sum.get_in_by_name("cin").unwrap();
+    let s = sum.get_out_bus_by_name("s").unwrap();
+    let cout = sum.get_out_by_name("cout").unwrap();
+    assert_eq!(cin, sum.get_in(16));
+    assert_eq!(b.size(), 8);
+    assert!(sum.get_in_by_name("c").is_none());
+    assert_eq!(sum.input_ports()[1].offset, 8);
+
+    a.set(0, 200u8);
+    b.set(0, 100u8);
+    cin.set(true);
+    sum.conduct();
+    let res: u8 = s.get(0);
+    assert_eq!(res, 45);
+    assert_eq!(cout.get(), true);
+    assert_eq!(
+        sum.to_string(),
+        "byte_sum(a:11001000,b:01100100,cin:1,)->(s:00101101,cout:1,)\n"
+    );
+    assert_eq!(sum.flatten().get_out_bus_by_name("s").unwrap().size(), 8);
+}
+
+#[test]
+pub fn test_lsb_first_ports() {
+    let sum = byte_sum();
+    let reversed = |bus: Bus| {
+        let mut wires = bus.wires().to_vec();
+        wires.reverse();
+        Bus::with_wires(wires).with_order(BitOrder::LsbFirst)
+    };
+    let mut named = Complex::new("byte_sum");
+    named
+        .add_named_input_bus("a", reversed(sum.get_in_bus(0, 8)))
+        .unwrap();
+    named
+        .add_named_input_bus("b", reversed(sum.get_in_bus(8, 8)))
+        .unwrap();
+    named.add_named_input("cin", sum.get_in(16)).unwrap();
+    named
+        .add_named_output_bus("s", reversed(sum.get_out_bus(0, 8)))
+        .unwrap();
+    named.add_complex(sum);
+
+    let a = named.get_in_bus_by_name("a").unwrap();
+    assert_eq!(a.order(), BitOrder::LsbFirst);
+    a.set(0, 0b0000_0011u8);
+    assert_eq!(a.get_wire(0).get(), true);
+    assert_eq!(named.get_in(1).get(), true);
+    assert_eq!(named.get_in(7).get(), false);
+    named.get_in_bus_by_name("b").unwrap().set(0, 10u8);
+    named.conduct();
+    let res: u8 = named.get_out_bus_by_name("s").unwrap().get(0);
+    assert_eq!(res, 13);
+    let res: u8 = named.get_out_bus(0, 8).get(0);
+    assert_eq!(res, 0b1011_0000);
+}
+
+#[test]
+pub fn test_duplicate_port() {
+    let mut complex = Complex::new("dup");
+    complex.add_named_input("a", Wire::new()).unwrap();
+    assert_eq!(
+        complex
+            .add_named_input("a", Wire::new())
+            .unwrap_err()
+            .to_string(),
+        "invalid port a of dup: duplicate name"
+    );
+    complex.add_named_output("a", Wire::new()).unwrap();
+    assert!(matches!(
+        complex.add_named_output_bus("b", Bus::new(0)),
+        Err(BinariiError::InvalidPort { name: "b", .. })
+    ));
+    assert_eq!(complex.input_len(), 1);
+    assert!(complex.get_out_by_name("b").is_none());
+}
+
+#[test]
+pub fn test_debug_dump() {
+    let complex = half_sum();
+    assert_eq!(format!("{:?}", complex), format!("{:?}", half_sum()));
+
+    complex.get_in(0).set_label("a");
+    complex.get_in(1).set_label("b");
+    complex.get_in(0).set(true);
+    complex.conduct();
+    assert_eq!(
+        format!("{:?}", complex),
+        "half_sum(a=1,b=0,)->(n2=1,n3=0,)\n  xor(a=1,b=0,)->(n2=1,)\n  and(a=1,b=0,)->(n3=0,)\nITERS:1\n"
+    );
+    assert_eq!(complex.to_string(), "half_sum(a:1,b:0,)->(1,0,)\n");
+    assert_eq!(complex.nets().len(), 4);
+
+    let nested = sum();
+    let dump = format!("{:?}", nested);
+    assert_eq!(dump, format!("{:?}", sum()));
+    assert!(dump.starts_with("sum(n0=0,n1=0,n2=0,)->(n3=0,n4=0,)\n"));
+    assert!(dump.contains("\n  half_sum("));
+}
@@ -0,0 +1,127 @@
+use crate::elements::complex::{half_sum, ring_oscillator};
+use binarii::elements::bus::{Bus, BusAccess};
+use binarii::elements::error::BinariiError;
+use binarii::elements::gate::Gate;
+use binarii::elements::wire::Wire;
+use std::error::Error;
+
+#[test]
+pub fn test_complex_ports_out_of_range() {
+    let complex = half_sum();
+    assert_eq!(complex.try_get_in(1).unwrap(), complex.get_in(1));
+    assert!(matches!(
+        complex.try_get_in(2),
+        Err(BinariiError::InputOutOfRange {
+            element: "half_sum",
+            index: 2,
+            len: 2
+        })
+    ));
+    assert!(matches!(
+        complex.try_get_out_bus(1, 2),
+        Err(BinariiError::OutputOutOfRange {
+            index: 2,
+            len: 2,
+            ..
+        })
+    ));
+    assert_eq!(complex.try_get_in_bus(0, 2).unwrap().size(), 2);
+    assert!(matches!(
+        complex.try_get_in_bus(usize::MAX, 2),
+        Err(BinariiError::InputOutOfRange { index, .. }) if index == usize::MAX - 1
+    ));
+    assert!(complex.try_get_out_bus(1, usize::MAX).is_err());
+}
+
+struct Nibble(u8);
+
+impl BusAccess<Nibble> for Bus {
+    fn set(&self, offset: usize, value: Nibble) {
+        self.set(offset, value.0 << 4);
+    }
+
+    fn get(&self, offset: usize) -> Nibble {
+        let value: u8 = self.get(offset);
+        Nibble(value >> 4)
+    }
+
+    fn try_set(&self, offset: usize, value: Nibble) -> Result<(), BinariiError> {
+        self.try_set(offset, value.0 << 4)
+    }
+
+    fn try_get(&self, offset: usize) -> Result<Nibble, BinariiError> {
+        let value: u8 = self.try_get(offset)?;
+        Ok(Nibble(value >> 4))
+    }
+}
+
+#[test]
+pub fn test_custom_try_access() {
+    let bus = Bus::new(8);
+    bus.try_set(0, Nibble(0b1010)).unwrap();
+    let value: u8 = bus.get(0);
+    assert_eq!(value, 0b1010_0000);
+    let nibble: Nibble = bus.try_get(0).unwrap();
+    assert_eq!(nibble.0, 0b1010);
+    assert!(bus.try_set(1, Nibble(0b1010)).is_err());
+    assert!(BusAccess::<Nibble>::try_get(&bus, 1).is_err());
+}
+
+#[test]
+pub fn test_unconnected_wire() {
+    let mut complex = half_sum();
+    let unused = Wire::new();
+    complex.add_input(unused.clone());
+    let err = complex.try_set_in(2, Wire::new()).unwrap_err();
+    assert!(matches!(err, BinariiError::UnconnectedWire { wire, .. } if wire == unused.id()));
+    assert_eq!(complex.get_in(2), unused);
+
+    let a = Wire::new();
+    complex.try_set_in(0, a.clone()).unwrap();
+    assert_eq!(complex.get_in(0), a);
+    complex.try_set_out(1, Wire::new()).unwrap();
+}
+
+#[test]
+pub fn test_bus_width() {
+    let bus = Bus::new(10);
+    bus.try_set(2, 0xA5u8).unwrap();
+    assert!(Bus::new(8).try_set(0, 1u16).is_err());
+    assert_eq!(BusAccess::<u8>::try_get(&bus, 2).unwrap(), 0xA5);
+    assert!(matches!(
+        bus.try_set(3, 0u8),
+        Err(BinariiError::WidthMismatch {
+            expected: 8,
+            found: 7
+        })
+    ));
+    assert!(BusAccess::<bool>::try_get(&bus, 10).is_err());
+    assert!(matches!(
+        bus.try_get_wire(12),
+        Err(BinariiError::WireOutOfRange { index: 12, len: 10 })
+    ));
+}
+
+#[test]
+pub fn test_gate_ports() {
+    let mut gate = Gate::and(Wire::new(), Wire::new(), Wire::new());
+    let wire = Wire::new();
+    gate.try_wire_in(1, wire.clone()).unwrap();
+    assert_eq!(gate.try_get_in(1).unwrap(), wire);
+    assert!(gate.try_wire_in(2, wire).is_err());
+    assert_eq!(
+        gate.try_get_in(3).unwrap_err().to_string(),
+        "and has 2 inputs, no input 3"
+    );
+    assert!(Gate::not(Wire::new(), Wire::new()).try_get_in(1).is_err());
+}
+
+#[test]
+pub fn test_oscillation_error() {
+    let ring = ring_oscillator();
+    let err = BinariiError::from(ring.settle().unwrap_err());
+    assert!(err.source().is_some());
+    assert!(err
+        .to_string()
+        .starts_with("ring_oscillator did not settle"));
+}
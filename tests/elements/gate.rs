@@ -1,4 +1,5 @@
-use binarii::elements::gate::Gate;
+use binarii::elements::gate::{Gate, GateKind};
+use binarii::elements::signal::Level;
 use binarii::elements::wire::Wire;
 use binarii::elements::Conduct;
 
@@ -52,3 +53,169 @@ pub fn test_not() {
     key.conduct();
     assert_eq!(out.get(), true);
 }
+
+#[test]
+pub fn test_xnor_buf_constant() {
+    let (in_1, in_2) = (Wire::new(), Wire::new());
+    let (xnor_out, buf_out) = (Wire::new(), Wire::new());
+    let xnor = Gate::xnor(in_1.clone(), in_2.clone(), xnor_out.clone());
+    let buf = Gate::buf(in_1.clone(), buf_out.clone());
+    assert_eq!(xnor_out.get(), true);
+    assert_eq!(buf_out.get(), false);
+
+    in_1.set(true);
+    xnor.conduct();
+    buf.conduct();
+    assert_eq!(xnor_out.get(), false);
+    assert_eq!(buf_out.get(), true);
+
+    in_1.set_level(Level::Z);
+    buf.conduct();
+    assert_eq!(buf_out.level(), Level::X);
+
+    let out = Wire::new();
+    Gate::constant(true, out.clone());
+    assert_eq!(out.get(), true);
+    let constant = Gate::constant(false, out.clone());
+    assert_eq!(out.get(), false);
+    assert_eq!(constant.tp(), "const0");
+}
+
+#[test]
+pub fn test_tri_state() {
+    let input = Wire::new();
+    let enable = Wire::new();
+    let out = Wire::new();
+    let key = Gate::tri_state(input.clone(), enable.clone(), out.clone());
+    assert_eq!(out.level(), Level::Z);
+    input.set(true);
+    key.conduct();
+    assert_eq!(out.level(), Level::Z);
+    enable.set(true);
+    key.conduct();
+    assert_eq!(out.level(), Level::High);
+    input.set(false);
+    key.conduct();
+    assert_eq!(out.level(), Level::Low);
+}
+
+#[test]
+pub fn test_unknown_propagation() {
+    let wire_1 = Wire::new();
+    let wire_2 = Wire::new();
+    let and_out = Wire::new();
+    let or_out = Wire::new();
+    let xor_out = Wire::new();
+    let and = Gate::and(wire_1.clone(), wire_2.clone(), and_out.clone());
+    let or = Gate::or(wire_1.clone(), wire_2.clone(), or_out.clone());
+    let xor = Gate::xor(wire_1.clone(), wire_2.clone(), xor_out.clone());
+    let conduct = || {
+        and.conduct();
+        or.conduct();
+        xor.conduct();
+    };
+
+    wire_1.set_level(Level::X);
+    wire_2.set(false);
+    conduct();
+    assert_eq!(and_out.level(), Level::Low);
+    assert_eq!(or_out.level(), Level::X);
+    assert_eq!(xor_out.level(), Level::X);
+
+    wire_1.set_level(Level::Z);
+    wire_2.set(true);
+    conduct();
+    assert_eq!(and_out.level(), Level::X);
+    assert_eq!(or_out.level(), Level::High);
+    assert_eq!(xor_out.level(), Level::X);
+
+    wire_1.set(true);
+    conduct();
+    assert_eq!(and_out.level(), Level::High);
+    assert_eq!(or_out.level(), Level::High);
+    assert_eq!(xor_out.level(), Level::Low);
+}
+
+#[test]
+pub fn test_n_input() {
+    let inputs = (0..4).map(|_| Wire::new()).collect::<Vec<_>>();
+    let outs = (0..5).map(|_| Wire::new()).collect::<Vec<_>>();
+    let gates = [
+        Gate::and_n(inputs.clone(), outs[0].clone()),
+        Gate::or_n(inputs.clone(), outs[1].clone()),
+        Gate::xor_n(inputs.clone(), outs[2].clone()),
+        Gate::nand_n(inputs.clone(), outs[3].clone()),
+        Gate::nor_n(inputs.clone(), outs[4].clone()),
+    ];
+    assert_eq!(gates[2].inputs().len(), 4);
+
+    for value in 0..16u32 {
+        for (i, wire) in inputs.iter().enumerate() {
+            wire.set(value >> i & 1 == 1);
+        }
+        for gate in gates.iter() {
+            gate.conduct();
+        }
+        assert_eq!(outs[0].get(), value == 15);
+        assert_eq!(outs[1].get(), value != 0);
+        assert_eq!(outs[2].get(), value.count_ones() % 2 == 1);
+        assert_eq!(outs[3].get(), value != 15);
+        assert_eq!(outs[4].get(), value == 0);
+    }
+}
+
+#[test]
+#[should_panic]
+pub fn test_n_input_empty() {
+    Gate::and_n(Vec::new(), Wire::new());
+}
+
+#[test]
+pub fn test_gate_kinds() {
+    let a = Wire::new();
+    let b = Wire::new();
+    let out = Wire::new();
+    for kind in GateKind::ALL {
+        assert_eq!(kind.name().parse::<GateKind>().unwrap(), kind);
+        let inputs = [a.clone(), b.clone()]
+            .into_iter()
+            .take(kind.arity().unwrap_or(2))
+            .collect::<Vec<_>>();
+        let gate = Gate::new(kind, inputs, out.clone());
+        assert_eq!(gate.kind(), kind);
+        assert_eq!(gate.tp(), kind.name());
+    }
+    assert!("mux".parse::<GateKind>().is_err());
+    assert!(Gate::try_new(GateKind::Not, vec![a.clone(), b.clone()], out.clone()).is_err());
+    assert_eq!(
+        Gate::try_new(GateKind::Or, Vec::new(), out.clone())
+            .err()
+            .unwrap()
+            .to_string(),
+        "or needs at least one input"
+    );
+
+    let truth = |kind: GateKind, x: bool, y: bool| {
+        a.set(x);
+        b.set(y);
+        Gate::new(kind, vec![a.clone(), b.clone()], out.clone());
+        out.get()
+    };
+    for (x, y) in [(false, false), (false, true), (true, false), (true, true)] {
+        assert_eq!(truth(GateKind::Xnor, x, y), x == y);
+        assert_eq!(truth(GateKind::Nand, x, y), !(x && y));
+    }
+
+    Gate::new(GateKind::Const(true), Vec::new(), out.clone());
+    assert_eq!(out.level(), Level::High);
+    b.set_level(Level::Z);
+    Gate::new(GateKind::Buf, vec![b.clone()], out.clone());
+    assert_eq!(out.level(), Level::X);
+    assert_eq!(Gate::not(a, out).inputs().len(), 1);
+}
+
+#[test]
+pub fn test_gate_display() {
+    let gate = Gate::and(Wire::named("a"), Wire::new(), Wire::new());
+    assert_eq!(gate.to_string(), "IN:a\nIN:in1\nOUT:out\nand(0, 0)->0\n");
+}
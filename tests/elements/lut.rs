@@ -0,0 +1,103 @@
+use binarii::elements::bus::{Bus, BusAccess};
+use binarii::elements::complex::Complex;
+use binarii::elements::error::BinariiError;
+use binarii::elements::lut::Lut;
+use binarii::elements::signal::Level;
+use binarii::elements::wire::Wire;
+use binarii::elements::Conduct;
+
+const FULL_ADDER: &str = "
+# a b c  s cout
+000 00
+100 10
+010 10
+110 01
+001 10
+101 01
+011 01
+111 11
+";
+
+#[test]
+pub fn test_lut_full_adder() {
+    let inputs = (0..3).map(|_| Wire::new()).collect::<Vec<_>>();
+    let outputs = (0..2).map(|_| Wire::new()).collect::<Vec<_>>();
+    let parsed = Lut::parse(inputs.clone(), outputs.clone(), FULL_ADDER).unwrap();
+    let generated = Lut::from_fn(inputs.clone(), outputs.clone(), |row| {
+        let sum = row.count_ones() as u64;
+        (sum & 1) | (sum >> 1) << 1
+    })
+    .unwrap();
+
+    for row in 0..8 {
+        assert_eq!(parsed.row(row), generated.row(row));
+        for (i, wire) in inputs.iter().enumerate() {
+            wire.set(row >> i & 1 == 1);
+        }
+        parsed.conduct();
+        let ones = row.count_ones();
+        assert_eq!(outputs[0].get(), ones % 2 == 1);
+        assert_eq!(outputs[1].get(), ones >= 2);
+    }
+}
+
+#[test]
+pub fn test_lut_in_complex() {
+    let a = Bus::new(4);
+    let out = Bus::new(8);
+    let mut decoder = Complex::new("decoder");
+    decoder.add_named_input_bus("a", a.clone()).unwrap();
+    decoder.add_named_output_bus("seg", out.clone()).unwrap();
+    let lut = Lut::from_fn(a.wires().to_vec(), out.wires().to_vec(), |row| {
+        1 << (row % 8)
+    })
+    .unwrap();
+    decoder.add_component(lut);
+
+    for value in 0..16u8 {
+        for i in 0..4 {
+            a.set(i, value >> i & 1 == 1);
+        }
+        decoder.conduct();
+        let seg: u8 = out.get(0);
+        assert_eq!(seg, 0x80 >> (value % 8));
+    }
+
+    a.get_wire(2).set_level(Level::X);
+    decoder.conduct();
+    assert_eq!(out.get_wire(0).level(), Level::X);
+}
+
+#[test]
+pub fn test_lut_undefined_rows() {
+    let input = Wire::new();
+    let output = Wire::new();
+    let lut = Lut::parse(vec![input.clone()], vec![output.clone()], "1 1\n").unwrap();
+    input.set(true);
+    lut.conduct();
+    assert_eq!(output.level(), Level::High);
+    input.set(false);
+    lut.conduct();
+    assert_eq!(output.level(), Level::X);
+}
+
+#[test]
+pub fn test_lut_errors() {
+    let wires = || (0..2).map(|_| Wire::new()).collect::<Vec<_>>();
+    assert!(matches!(
+        Lut::new(wires(), wires(), &[0, 1, 2]),
+        Err(BinariiError::WidthMismatch {
+            expected: 4,
+            found: 3
+        })
+    ));
+    assert!(matches!(
+        Lut::parse(wires(), wires(), "00 00\n0x 01\n"),
+        Err(BinariiError::InvalidTable { line: 2, .. })
+    ));
+    assert!(matches!(
+        Lut::parse(wires(), wires(), "00 00\n00 11"),
+        Err(BinariiError::InvalidTable { line: 2, .. })
+    ));
+    assert!(Lut::parse(wires(), wires(), "000 00").is_err());
+}
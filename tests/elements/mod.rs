@@ -1,4 +1,13 @@
+pub mod builder;
 pub mod bus;
 pub mod complex;
+pub mod error;
 pub mod gate;
+pub mod lut;
+pub mod netlist;
+pub mod observer;
 pub mod oscillator;
+pub mod program;
+pub mod snapshot;
+pub mod timing;
+pub mod wire;
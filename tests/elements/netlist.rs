@@ -0,0 +1,49 @@
+use crate::elements::complex::byte_sum;
+use binarii::elements::bus::BusAccess;
+use binarii::elements::complex::Element;
+use binarii::elements::netlist::Netlist;
+use binarii::elements::Conduct;
+
+#[test]
+pub fn test_flatten_byte_sum() {
+    let sum = byte_sum();
+    let netlist = Netlist::new(&sum);
+    assert_eq!(netlist.gate_count(), 40);
+    assert_eq!(netlist.count("xor"), 16);
+    assert_eq!(netlist.count("and"), 16);
+    assert_eq!(netlist.count("or"), 8);
+
+    let flat = netlist.complex();
+    assert_eq!(flat.input_len(), 17);
+    assert_eq!(flat.output_len(), 9);
+    for (id, element) in flat.elements() {
+        assert!(matches!(element, Element::Gate(_)));
+        let path = netlist.path(id).unwrap();
+        let segments = path.split('/').collect::<Vec<_>>();
+        assert_eq!(segments[0], "byte_sum");
+        assert!(segments[1].starts_with("sum#"));
+        assert!(segments.last().unwrap().starts_with(element.tp()));
+        if element.tp() == "or" {
+            assert_eq!(segments.len(), 3);
+        } else {
+            assert!(segments[2].starts_with("half_sum#"));
+            assert_eq!(segments.len(), 4);
+        }
+    }
+
+    let flat = sum.flatten().instantiate();
+    let a = flat.get_in_bus(0, 8);
+    let b = flat.get_in_bus(8, 8);
+    let res = flat.get_out_bus(0, 8);
+    let carry_out = flat.get_out(8);
+    for (i, j) in [(0u8, 0u8), (1, 2), (200, 100), (255, 255), (85, 170)] {
+        a.set(0, i);
+        b.set(0, j);
+        flat.get_in(16).set(false);
+        flat.conduct();
+        let (expected_sum, expected_carry) = i.overflowing_add(j);
+        let res: u8 = res.get(0);
+        assert_eq!(res, expected_sum);
+        assert_eq!(carry_out.get(), expected_carry);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::elements::complex::{byte_sum, half_sum};
+use binarii::elements::bus::BusAccess;
+use binarii::elements::complex::{Complex, Mode};
+use binarii::elements::observer::WireChange;
+use binarii::elements::signal::Signal;
+use binarii::elements::Conduct;
+use std::sync::{Arc, Mutex};
+
+#[test]
+pub fn test_observe_wire() {
+    let mut complex = half_sum();
+    let changes = Arc::new(Mutex::new(Vec::<WireChange>::new()));
+    let log = changes.clone();
+    let s = complex.get_out(0);
+    let id = complex.observe(&s, move |change| log.lock().unwrap().push(change.clone()));
+
+    complex.conduct();
+    assert!(changes.lock().unwrap().is_empty());
+
+    complex.get_in(0).set(true);
+    complex.conduct();
+    complex.conduct();
+    complex.get_in(1).set(true);
+    complex.conduct();
+    assert_eq!(complex.ticks(), 4);
+    {
+        let changes = changes.lock().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].tick, 2);
+        assert_eq!(changes[0].wire, s);
+        assert_eq!(changes[0].old, Signal::LOW);
+        assert_eq!(changes[0].new, Signal::HIGH);
+        assert_eq!(changes[1].tick, 4);
+        assert_eq!(changes[1].new, Signal::LOW);
+    }
+
+    assert_eq!(complex.unobserve(id), true);
+    assert_eq!(complex.unobserve(id), false);
+    complex.get_in(1).set(false);
+    complex.conduct();
+    assert_eq!(changes.lock().unwrap().len(), 2);
+}
+
+#[test]
+pub fn test_observe_bus() {
+    let mut sum = byte_sum();
+    let res = sum.get_out_bus(0, 8);
+    let changed = Arc::new(Mutex::new(0));
+    let count = changed.clone();
+    sum.observe_bus(&res, move |_| *count.lock().unwrap() += 1);
+
+    sum.get_in_bus(0, 8).set(0, 0b0000_0101u8);
+    sum.conduct();
+    assert_eq!(*changed.lock().unwrap(), 2);
+    sum.get_in_bus(8, 8).set(0, 0b0000_0011u8);
+    sum.conduct();
+    let res: u8 = res.get(0);
+    assert_eq!(res, 8);
+    assert_eq!(*changed.lock().unwrap(), 5);
+}
+
+#[test]
+pub fn test_observe_sub_complex() {
+    for mode in [Mode::Iterative, Mode::EventDriven] {
+        let mut inner = half_sum();
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let log = ticks.clone();
+        inner.observe(&inner.get_out(0), move |change| {
+            log.lock().unwrap().push(change.tick)
+        });
+        let a = inner.get_in(0);
+        let b = inner.get_in(1);
+
+        let mut outer = Complex::new("outer");
+        outer.add_input(a.clone());
+        outer.add_input(b.clone());
+        outer.add_output(inner.get_out(0));
+        outer.add_complex(inner);
+        outer.set_mode(mode);
+
+        a.set(true);
+        outer.conduct();
+        b.set(true);
+        outer.conduct();
+        a.set(false);
+        outer.settle().unwrap();
+        assert_eq!(outer.ticks(), 3);
+        assert_eq!(*ticks.lock().unwrap(), [1, 2, 3]);
+    }
+}
@@ -1,3 +1,5 @@
+use binarii::elements::component::Component;
+use binarii::elements::error::BinariiError;
 use binarii::elements::oscillator::Oscillator;
 use binarii::elements::wire::Wire;
 use binarii::elements::Conduct;
@@ -39,3 +41,52 @@ pub fn test_oscillator_1() {
     osc.conduct();
     assert_eq!(wire_out.get(), false);
 }
+
+#[test]
+pub fn test_oscillator_instantiate() {
+    let wire_out = Wire::new();
+    let osc = Oscillator::new(wire_out.clone(), 2);
+    osc.conduct();
+    let copy = osc.instantiate();
+    assert_ne!(copy.get_out(), wire_out);
+    copy.conduct();
+    assert_eq!(copy.get_out().get(), true);
+    assert_eq!(wire_out.get(), false);
+    osc.conduct();
+    assert_eq!(wire_out.get(), true);
+}
+
+#[test]
+pub fn test_oscillator_ports() {
+    let mut osc = Oscillator::new(Wire::new(), 2);
+    assert!(matches!(
+        osc.wire_input(0, Wire::new()),
+        Err(BinariiError::InputOutOfRange { len: 0, .. })
+    ));
+    assert!(osc.wire_output(1, Wire::new()).is_err());
+    let out = Wire::new();
+    osc.wire_output(0, out.clone()).unwrap();
+    assert_eq!(osc.get_out(), out);
+
+    let copy = osc.box_instantiate();
+    osc.conduct();
+    copy.conduct();
+    assert_eq!(out.get(), false);
+    osc.conduct();
+    assert_eq!(out.get(), true);
+}
+
+#[test]
+pub fn test_oscillator_load() {
+    let osc = Oscillator::new(Wire::new(), 2);
+    osc.conduct();
+    let state = osc.save();
+    assert!(matches!(
+        osc.load(&state[..3]),
+        Err(BinariiError::InvalidSnapshot(_))
+    ));
+    assert_eq!(osc.save(), state);
+    osc.conduct();
+    osc.load(&state).unwrap();
+    assert_eq!(osc.save(), state);
+}
@@ -0,0 +1,145 @@
+use crate::elements::complex::{byte_flip_flop, byte_sum, shared_bus};
+use binarii::elements::bus::{BusAccess, LaneAccess};
+use binarii::elements::complex::Complex;
+use binarii::elements::error::BinariiError;
+use binarii::elements::gate::Gate;
+use binarii::elements::oscillator::Oscillator;
+use binarii::elements::signal::Level;
+use binarii::elements::timing::Timing;
+use binarii::elements::wire::Wire;
+use binarii::elements::Conduct;
+
+#[test]
+pub fn test_byte_sum_program() {
+    let program = byte_sum().program().unwrap();
+    assert_eq!(program.instructions().len(), 8 * 5);
+
+    let a = program.get_in_bus(0, 8);
+    let b = program.get_in_bus(8, 8);
+    let carry_in = program.get_in(16);
+    let res = program.get_out_bus(0, 8);
+    let carry_out = program.get_out(8);
+
+    for i in 0..255u8 {
+        for j in 0..255u8 {
+            a.set(0, i);
+            b.set(0, j);
+            carry_in.set(false);
+            program.conduct();
+            let res: u8 = res.get(0);
+            let carry = carry_out.get();
+            let (expected_sum, expected_carry) = i.overflowing_add(j);
+            assert_eq!(res, expected_sum);
+            assert_eq!(carry, expected_carry);
+        }
+    }
+}
+
+#[test]
+pub fn test_byte_flip_flop_program() {
+    let complex = byte_flip_flop();
+    let program = byte_flip_flop().program().unwrap();
+
+    let steps: [(u8, bool); 8] = [
+        (0, true),
+        (0, false),
+        (0b11111111, false),
+        (0b11111111, true),
+        (0b11111111, false),
+        (0b10101010, false),
+        (0b10101010, true),
+        (0b01010101, false),
+    ];
+
+    for (data, clk) in steps {
+        complex.get_in_bus(0, 8).set(0, data);
+        complex.get_in(8).set(clk);
+        complex.conduct();
+        program.get_in_bus(0, 8).set(0, data);
+        program.get_in(8).set(clk);
+        program.conduct();
+
+        let expected: u8 = complex.get_out_bus(0, 8).get(0);
+        let actual: u8 = program.get_out_bus(0, 8).get(0);
+        assert_eq!(actual, expected);
+        assert_eq!(program.get_out(8).get(), clk);
+    }
+}
+
+#[test]
+pub fn test_program_lanes() {
+    let program = byte_sum().program().unwrap();
+    let operands = (0..64u8).collect::<Vec<_>>();
+    program.get_in_bus(0, 8).set_lanes(0, &operands);
+    program.get_in_bus(8, 8).set_lanes(0, &operands);
+    program.conduct();
+
+    let results: [u8; 64] = program.get_out_bus(0, 8).get_lanes(0);
+    for (lane, value) in operands.iter().enumerate() {
+        assert_eq!(results[lane], value * 2);
+    }
+}
+
+#[test]
+pub fn test_program_shared_bus() {
+    let program = shared_bus().program().unwrap();
+    let a = program.get_in_bus(0, 8);
+    let b = program.get_in_bus(8, 8);
+    let enable_a = program.get_in(16);
+    let enable_b = program.get_in(17);
+    let shared = program.get_out_bus(0, 8);
+
+    a.set(0, 0b11110000u8);
+    b.set(0, 0b10101010u8);
+    enable_b.set(true);
+    assert!(program.settle().is_ok());
+    let value: u8 = shared.get(0);
+    assert_eq!(value, 0b10101010);
+
+    enable_a.set(true);
+    program.conduct();
+    assert_eq!(shared.get_wire(0).level(), Level::High);
+    assert_eq!(shared.get_wire(1).level(), Level::X);
+    assert_eq!(shared.get_wire(7).level(), Level::Low);
+
+    enable_b.set(false);
+    program.conduct();
+    let value: u8 = shared.get(0);
+    assert_eq!(value, 0b11110000);
+}
+
+#[test]
+pub fn test_program_oscillation() {
+    let mut ring = Complex::new("ring");
+    let wire = Wire::new();
+    ring.add_output(wire.clone());
+    ring.add_gate(Gate::not(wire.clone(), wire.clone()));
+    ring.set_settle_limit(5);
+
+    let oscillation = ring.program().unwrap().settle().unwrap_err();
+    assert_eq!(oscillation.complex, "ring");
+    assert_eq!(oscillation.iterations, 5);
+    assert_eq!(oscillation.wires, vec![wire]);
+}
+
+#[test]
+pub fn test_program_rejects_components() {
+    let clk = Wire::new();
+    let out = Wire::new();
+    let mut complex = Complex::new("divider");
+    complex.add_output(out.clone());
+    complex.add_component(Oscillator::new(clk.clone(), 3));
+    complex.add_gate(Gate::not(clk, out));
+
+    assert!(matches!(
+        complex.program(),
+        Err(BinariiError::UnsupportedComponent {
+            model: "program",
+            component: "oscillator"
+        })
+    ));
+    assert_eq!(
+        Timing::new(&complex).err().unwrap().to_string(),
+        "timing cannot simulate component oscillator"
+    );
+}
@@ -0,0 +1,100 @@
+use crate::elements::complex::{byte_sum, half_sum};
+use binarii::elements::bus::BusAccess;
+use binarii::elements::complex::Complex;
+use binarii::elements::error::BinariiError;
+use binarii::elements::gate::Gate;
+use binarii::elements::oscillator::Oscillator;
+use binarii::elements::snapshot::Snapshot;
+use binarii::elements::wire::Wire;
+use binarii::elements::Conduct;
+
+#[test]
+pub fn test_snapshot_restore() {
+    let sum = byte_sum();
+    let a = sum.get_in_bus(0, 8);
+    let b = sum.get_in_bus(8, 8);
+    let res = sum.get_out_bus(0, 8);
+
+    a.set(0, 100u8);
+    b.set(0, 23u8);
+    sum.conduct();
+    let snapshot = sum.snapshot();
+
+    a.set(0, 1u8);
+    b.set(0, 1u8);
+    sum.conduct();
+    let value: u8 = res.get(0);
+    assert_eq!(value, 2);
+
+    let bytes = snapshot.to_bytes();
+    let decoded = Snapshot::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, snapshot);
+    sum.restore(&decoded).unwrap();
+    let (a_value, value): (u8, u8) = (a.get(0), res.get(0));
+    assert_eq!(a_value, 100);
+    assert_eq!(value, 123);
+
+    let copy = sum.instantiate();
+    copy.restore(&snapshot).unwrap();
+    let value: u8 = copy.get_out_bus(0, 8).get(0);
+    assert_eq!(value, 123);
+}
+
+#[test]
+pub fn test_snapshot_oscillator() {
+    let clk = Wire::new();
+    let out = Wire::new();
+    let mut complex = Complex::new("divider");
+    complex.add_output(out.clone());
+    complex.add_component(Oscillator::new(clk.clone(), 3));
+    complex.add_gate(Gate::not(clk.clone(), out.clone()));
+
+    complex.conduct();
+    let snapshot = complex.snapshot();
+    let mut trace = Vec::new();
+    for _ in 0..6 {
+        complex.conduct();
+        trace.push(clk.get());
+    }
+
+    complex.restore(&snapshot).unwrap();
+    for value in trace {
+        complex.conduct();
+        assert_eq!(clk.get(), value);
+    }
+}
+
+#[test]
+pub fn test_snapshot_mismatch() {
+    let snapshot = half_sum().snapshot();
+    assert!(matches!(
+        byte_sum().restore(&snapshot),
+        Err(BinariiError::InvalidSnapshot(_))
+    ));
+
+    let bytes = snapshot.to_bytes();
+    assert!(Snapshot::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Snapshot::from_bytes(b"junk").is_err());
+    assert_eq!(snapshot.signals().len(), 4);
+}
+
+#[test]
+pub fn test_snapshot_rollback() {
+    let mut complex = Complex::new("clocks");
+    complex.add_component(Oscillator::new(Wire::new(), 2));
+    complex.add_component(Oscillator::new(Wire::new(), 3));
+    complex.conduct();
+    let mut bytes = complex.snapshot().to_bytes();
+    bytes.truncate(bytes.len() - 12);
+    bytes.extend(3u64.to_le_bytes());
+    bytes.extend([0, 0, 0]);
+    let malformed = Snapshot::from_bytes(&bytes).unwrap();
+
+    complex.conduct();
+    let current = complex.snapshot();
+    assert!(matches!(
+        complex.restore(&malformed),
+        Err(BinariiError::InvalidSnapshot(_))
+    ));
+    assert_eq!(complex.snapshot(), current);
+}
@@ -0,0 +1,153 @@
+use crate::elements::complex::{byte_sum, shared_bus};
+use binarii::elements::bus::{Bus, BusAccess};
+use binarii::elements::complex::Complex;
+use binarii::elements::gate::Gate;
+use binarii::elements::signal::{Level, Signal};
+use binarii::elements::timing::Timing;
+use binarii::elements::wire::Wire;
+
+fn set_u8(timing: &mut Timing, bus: &Bus, value: u8) {
+    for i in 0..8 {
+        timing.set(&bus.get_wire(i), (value >> (7 - i) & 1) == 1);
+    }
+}
+
+#[test]
+pub fn test_hazard_glitch() {
+    let mut hazard = Complex::new("hazard");
+    let a = Wire::new();
+    let not_a = Wire::new();
+    let out = Wire::new();
+    hazard.add_input(a.clone());
+    hazard.add_output(out.clone());
+    hazard.add_gate(Gate::not(a.clone(), not_a.clone()));
+    hazard.add_gate(Gate::and(a.clone(), not_a.clone(), out.clone()));
+
+    let mut timing = Timing::new(&hazard).unwrap();
+    timing.settle(100).unwrap();
+    assert!(timing.trace().is_empty());
+
+    let start = timing.time();
+    timing.set(&a, true);
+    assert_eq!(timing.settle(100), Some(start + 2));
+    assert_eq!(out.get(), false);
+
+    let pulse = timing
+        .changes(&out)
+        .map(|c| (c.time - start, c.value))
+        .collect::<Vec<_>>();
+    assert_eq!(pulse, vec![(1, Signal::HIGH), (2, Signal::LOW)]);
+}
+
+#[test]
+pub fn test_byte_sum_ripple_time() {
+    let mut sum = byte_sum();
+    let a = sum.get_in_bus(0, 8);
+    let b = sum.get_in_bus(8, 8);
+    let res = sum.get_out_bus(0, 8);
+    let carry = sum.get_out(8);
+
+    let mut timing = Timing::new(&sum).unwrap();
+    timing.settle(1000).unwrap();
+
+    let start = timing.time();
+    set_u8(&mut timing, &a, 1);
+    let short = timing.settle(1000).unwrap() - start;
+    let value: u8 = res.get(0);
+    assert_eq!(value, 1);
+
+    let start = timing.time();
+    set_u8(&mut timing, &b, 255);
+    let ripple = timing.settle(1000).unwrap() - start;
+    let value: u8 = res.get(0);
+    assert_eq!(value, 0);
+    assert_eq!(carry.get(), true);
+    assert!(ripple > short * 4);
+
+    sum.set_delay(3);
+    let mut slow = Timing::new(&sum).unwrap();
+    slow.settle(1000).unwrap();
+    set_u8(&mut slow, &b, 0);
+    slow.settle(1000).unwrap();
+    let start = slow.time();
+    set_u8(&mut slow, &b, 255);
+    assert_eq!(slow.settle(1000).unwrap() - start, ripple * 3);
+}
+
+#[test]
+pub fn test_ring_oscillator_never_settles() {
+    let mut ring = Complex::new("ring");
+    let a = Wire::new();
+    let b = Wire::new();
+    let c = Wire::new();
+    ring.add_gate(Gate::not(a.clone(), b.clone()).with_delay(2));
+    ring.add_gate(Gate::not(b.clone(), c.clone()).with_delay(2));
+    ring.add_gate(Gate::not(c.clone(), a.clone()).with_delay(2));
+
+    let mut timing = Timing::new(&ring).unwrap();
+    assert_eq!(timing.settle(100), None);
+    assert!(!timing.is_idle());
+
+    timing.clear_trace();
+    timing.run_until(timing.time() + 24);
+    assert_eq!(timing.changes(&a).count(), 4);
+}
+
+#[test]
+pub fn test_drive_pulled_wire() {
+    let mut buffer = Complex::new("buffer");
+    let a = Wire::new();
+    let out = Wire::new();
+    a.pull_up();
+    buffer.add_input(a.clone());
+    buffer.add_output(out.clone());
+    buffer.add_gate(Gate::buf(a.clone(), out.clone()));
+
+    let mut timing = Timing::new(&buffer).unwrap();
+    timing.set_signal(&a, Signal::Z);
+    timing.settle(100).unwrap();
+    assert_eq!(out.get(), true);
+    timing.set(&a, true);
+    timing.settle(100).unwrap();
+    a.set_pull(None);
+    assert_eq!(a.signal(), Signal::HIGH);
+}
+
+#[test]
+pub fn test_shared_bus_timing() {
+    let complex = shared_bus();
+    let a = complex.get_in_bus(0, 8);
+    let b = complex.get_in_bus(8, 8);
+    let enable_a = complex.get_in(16);
+    let enable_b = complex.get_in(17);
+    let shared = complex.get_out_bus(0, 8);
+
+    let mut timing = Timing::new(&complex).unwrap();
+    set_u8(&mut timing, &a, 0b1111_1111);
+    set_u8(&mut timing, &b, 0b1010_1010);
+    timing.set(&enable_b, true);
+    timing.settle(100).unwrap();
+    let value: u8 = shared.get(0);
+    assert_eq!(value, 0b1010_1010);
+    assert!(timing.contention().is_empty());
+
+    timing.set(&enable_a, true);
+    timing.settle(100).unwrap();
+    let contention = timing.contention();
+    assert_eq!(contention.len(), 4);
+    for i in 0..8 {
+        let wire = shared.get_wire(i);
+        if i % 2 == 0 {
+            assert_eq!(wire.level(), Level::High);
+        } else {
+            assert_eq!(wire.level(), Level::X);
+            assert!(contention.contains(&wire));
+        }
+    }
+
+    timing.set(&enable_b, false);
+    timing.settle(100).unwrap();
+    assert!(timing.contention().is_empty());
+    let value: u8 = shared.get(0);
+    assert_eq!(value, 0b1111_1111);
+}
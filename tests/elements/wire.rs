@@ -0,0 +1,159 @@
+use binarii::elements::signal::{Level, Pull, Signal, Signals};
+use binarii::elements::wire::Wire;
+
+#[test]
+pub fn test_wire_handle() {
+    let wire = Wire::new();
+    let handle = wire.clone();
+    assert_eq!(wire.id(), handle.id());
+    assert_ne!(wire.id(), Wire::new().id());
+
+    handle.set(true);
+    assert_eq!(wire.get(), true);
+    assert_eq!(Signals::global().get(wire.id()), Some(Signal::HIGH));
+
+    let restored = Wire::from_id(wire.id()).unwrap();
+    assert_eq!(restored, wire);
+    assert!(Signals::global().set(restored.id(), Signal::LOW));
+    assert_eq!(wire.get(), false);
+}
+
+#[test]
+pub fn test_wire_across_threads() {
+    let wire = Wire::new();
+    let handle = wire.clone();
+    std::thread::spawn(move || handle.set(true)).join().unwrap();
+    assert_eq!(wire.get(), true);
+}
+
+#[test]
+pub fn test_wire_lanes() {
+    let wire = Wire::new();
+    wire.set_lanes(0b1010);
+    assert_eq!(wire.get(), false);
+    assert_eq!(wire.get_lane(1), true);
+    assert_eq!(wire.get_lane(2), false);
+
+    wire.set_lane(0, true);
+    wire.set_lane(3, false);
+    assert_eq!(wire.lanes(), 0b0011);
+    assert_eq!(wire.get(), true);
+
+    wire.set(true);
+    assert_eq!(wire.lanes(), u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "lane 64 out of 64")]
+pub fn test_lane_out_of_range() {
+    Wire::new().set_lane(64, true);
+}
+
+#[test]
+pub fn test_wire_levels() {
+    let wire = Wire::new();
+    assert_eq!(wire.level(), Level::Low);
+    wire.set_level(Level::Z);
+    assert_eq!(wire.to_string(), "z");
+    assert_eq!(wire.signal(), Signal::Z);
+
+    let mut signal = Signal::LOW;
+    signal.set_level(1, Level::High);
+    signal.set_level(2, Level::X);
+    wire.set_signal(signal);
+    assert_eq!(wire.level(), Level::Low);
+    assert_eq!(wire.signal().level(1), Level::High);
+    assert_eq!(wire.signal().level(2), Level::X);
+    assert_eq!(wire.lanes(), 0b10);
+    assert_eq!(wire.get_lane(2), false);
+
+    wire.set_level(Level::X);
+    assert_eq!(wire.get(), false);
+
+    wire.set_lane(2, true);
+    assert_eq!(wire.signal().level(2), Level::High);
+}
+
+#[test]
+pub fn test_resolve() {
+    let (signal, fighting) = Signal::resolve(&[Signal::Z, Signal::HIGH, Signal::Z]);
+    assert_eq!(signal, Signal::HIGH);
+    assert_eq!(fighting, 0);
+
+    let (signal, fighting) = Signal::resolve(&[Signal::Z, Signal::Z]);
+    assert_eq!(signal, Signal::Z);
+    assert_eq!(fighting, 0);
+
+    let (signal, fighting) = Signal::resolve(&[Signal::LOW, Signal::known(0b10)]);
+    assert_eq!(signal.level(0), Level::Low);
+    assert_eq!(signal.level(1), Level::X);
+    assert_eq!(fighting, 0b10);
+
+    let (signal, fighting) = Signal::resolve(&[Signal::X, Signal::Z]);
+    assert_eq!(signal, Signal::X);
+    assert_eq!(fighting, 0);
+}
+
+#[test]
+pub fn test_wire_labels() {
+    let wire = Wire::named("carry");
+    assert_eq!(wire.label(), Some("carry".to_string()));
+    assert_eq!(wire.clone().name(), "carry");
+    assert_eq!(format!("{:?}", wire), "carry=0");
+    assert_eq!(format!("{:?}", Wire::new()), "0");
+
+    let other = Wire::from_id(wire.id()).unwrap();
+    other.set_label("cout");
+    assert_eq!(wire.name(), "cout");
+    wire.clear_label();
+    assert_eq!(other.label(), None);
+    assert_eq!(other.name(), other.id().to_string());
+}
+
+#[test]
+pub fn test_wire_pull() {
+    let wire = Wire::new();
+    wire.set_level(Level::Z);
+    wire.pull_up();
+    assert_eq!(wire.pull(), Some(Pull::Up));
+    assert_eq!(wire.level(), Level::High);
+    assert_eq!(wire.get(), true);
+
+    wire.set(false);
+    assert_eq!(wire.level(), Level::Low);
+
+    let mut signal = Signal::Z;
+    signal.set_level(1, Level::X);
+    wire.set_signal(signal);
+    wire.pull_down();
+    assert_eq!(wire.signal().level(0), Level::Low);
+    assert_eq!(wire.signal().level(1), Level::X);
+
+    wire.set_pull(None);
+    assert_eq!(wire.level(), Level::Z);
+}
+
+#[test]
+pub fn test_wire_release() {
+    let wire = Wire::named("temp");
+    wire.pull_up();
+    wire.set(true);
+    let id = wire.id();
+    let handle = wire.clone();
+    drop(wire);
+    assert_eq!(Wire::from_id(id), Some(handle.clone()));
+    drop(handle);
+
+    let live = Signals::global().len();
+    for _ in 0..100_000 {
+        let wire = Wire::named("temp");
+        wire.pull_down();
+        wire.set(true);
+        drop(wire);
+        let fresh = Wire::new();
+        assert_eq!(fresh.label(), None);
+        assert_eq!(fresh.pull(), None);
+        assert_eq!(fresh.level(), Level::Low);
+    }
+    assert!(Signals::global().len() < live + 50_000);
+}